@@ -0,0 +1,8 @@
+# Format described in ../data/README.md, see also https://example.com/spec
+URL = "https://example.com/not-a-comment"
+
+
+def main():
+    # mlc-disable-next-line
+    # ./does-not-exist.md
+    print(URL)  # ./example.ts
@@ -0,0 +1,4 @@
+/**
+ * Implements https://example.com/rfc (see ./example.py)
+ */
+export const url = "https://example.com/not-a-comment"; // ../data/project.toml
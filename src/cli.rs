@@ -99,6 +99,38 @@ pub fn parse_args() -> Config {
                 .action(ArgAction::Append)
                 .required(false),
         )
+        .arg(
+            Arg::new("max-concurrency")
+                .long("max-concurrency")
+                .value_name("COUNT")
+                .help("Maximum number of links which are checked at the same time")
+                .required(false),
+        )
+        .arg(
+            Arg::new("max-concurrency-per-host")
+                .long("max-concurrency-per-host")
+                .value_name("COUNT")
+                .help("Maximum number of web requests to the same host at the same time")
+                .required(false),
+        )
+        .arg(
+            Arg::new("accept-status")
+                .long("accept-status")
+                .value_name("STATUS")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Comma separated list of status codes or ranges which are accepted in addition to success and redirection. Example: --accept-status 403,999,400-429")
+                .required(false),
+        )
+        .arg(
+            Arg::new("status-map")
+                .long("status-map")
+                .value_name("STATUS=RESULT")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Comma separated list of status codes or ranges with the result 'ok', 'warn', 'ignore' or 'error'. The first match is used. Example: --status-map 401=ignore,403=warn,5xx=error")
+                .required(false),
+        )
         .arg(
             Arg::new("root-dir")
                 .long("root-dir")
@@ -152,6 +184,125 @@ pub fn parse_args() -> Config {
                 .action(ArgAction::Append)
                 .required(false),
         )
+        .arg(
+            Arg::new("check-web-anchors")
+                .long("check-web-anchors")
+                .value_name("SEVERITY")
+                .help("Download web pages to check that link fragments exist as anchors. Report missing anchors as 'warn' or 'error'")
+                .required(false),
+        )
+        .arg(
+            Arg::new("ignore-anchors")
+                .long("ignore-anchors")
+                .value_name("ANCHORS")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Comma separated list of web link fragments which will not be checked")
+                .required(false),
+        )
+        .arg(
+            Arg::new("slug-style")
+                .long("slug-style")
+                .value_name("STYLE")
+                .help("Algorithm used to create the anchors of markdown headings. Possible values: github, gitlab, mdbook, docusaurus, pandoc")
+                .required(false),
+        )
+        .arg(
+            Arg::new("autolink-literals")
+                .long("autolink-literals")
+                .action(ArgAction::SetTrue)
+                .help("Check bare URLs like https://example.com or www.example.com in markdown text")
+                .required(false),
+        )
+        .arg(
+            Arg::new("code-block-languages")
+                .long("code-block-languages")
+                .value_name("LANGUAGES")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Comma separated list of languages of fenced markdown code blocks in which URLs shall be checked. For example 'sh,bash'")
+                .required(false),
+        )
+        .arg(
+            Arg::new("code-block-severity")
+                .long("code-block-severity")
+                .value_name("SEVERITY")
+                .help("Report broken links of code blocks as 'warn' or 'error'")
+                .required(false),
+        )
+        .arg(
+            Arg::new("front-matter-keys")
+                .long("front-matter-keys")
+                .value_name("KEYS")
+                .value_delimiter(',')
+                .action(ArgAction::Append)
+                .help("Comma separated list of markdown front matter keys whose values shall be checked. For example 'image,canonical,redirect_from'")
+                .required(false),
+        )
+        .arg(
+            Arg::new("wiki-links")
+                .long("wiki-links")
+                .value_name("RESOLUTION")
+                .help("Check wiki links like [[Page]] in markdown files. Resolve them as 'shortest' unique path, relative to the 'root' of the vault or 'relative' to the linking file")
+                .required(false),
+        )
+        .arg(
+            Arg::new("structured-data")
+                .long("structured-data")
+                .value_name("FILES=KEYS")
+                .action(ArgAction::Append)
+                .help("Key paths of YAML, JSON or TOML files whose values shall be checked. For example '*Cargo.toml=package.homepage,package.repository'. Can be given multiple times")
+                .required(false),
+        )
+        .arg(
+            Arg::new("retries")
+                .long("retries")
+                .value_name("COUNT")
+                .help("Number of retries of web requests which failed with a timeout, a connection error or the status 429, 502, 503 or 504")
+                .required(false),
+        )
+        .arg(
+            Arg::new("retry-delay")
+                .long("retry-delay")
+                .value_name("DELAY-MS")
+                .help("Wait time in milliseconds before the first retry. It is doubled for each further retry")
+                .required(false),
+        )
+        .arg(
+            Arg::new("retry-max-wait")
+                .long("retry-max-wait")
+                .value_name("WAIT-MS")
+                .help("Maximum time in milliseconds waited for retries of a web request")
+                .required(false),
+        )
+        .arg(
+            Arg::new("connect-timeout")
+                .long("connect-timeout")
+                .value_name("TIMEOUT-MS")
+                .help("Maximum time in milliseconds to connect to a web server. Zero disables the timeout")
+                .required(false),
+        )
+        .arg(
+            Arg::new("read-timeout")
+                .long("read-timeout")
+                .value_name("TIMEOUT-MS")
+                .help("Maximum time in milliseconds in between two reads of a web response. Zero disables the timeout")
+                .required(false),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_name("TIMEOUT-MS")
+                .help("Maximum time in milliseconds of a web request. Zero disables the timeout")
+                .required(false),
+        )
+        .arg(
+            Arg::new("timeout-severity")
+                .long("timeout-severity")
+                .value_name("SEVERITY")
+                .help("Report timed out web requests as 'warn' or 'error'")
+                .required(false),
+        )
         .get_matches();
 
     let default_dir = format!(".{}", &MAIN_SEPARATOR);
@@ -178,6 +329,14 @@ pub fn parse_args() -> Config {
         opt.throttle = Some(throttle);
     }
 
+    if let Some(max) = matches.get_one::<String>("max-concurrency") {
+        opt.max_concurrency = Some(max.parse().expect("invalid max concurrency"));
+    }
+
+    if let Some(max) = matches.get_one::<String>("max-concurrency-per-host") {
+        opt.max_concurrency_per_host = Some(max.parse().expect("invalid max concurrency per host"));
+    }
+
     if let Some(f) = matches.get_one::<String>("csv") {
         opt.csv_file = Some(Path::new(&normalize_path_separators(f)).to_path_buf());
     }
@@ -249,6 +408,90 @@ pub fn parse_args() -> Config {
         opt.http_headers = Some(http_headers.map(|x| x.to_string()).collect());
     }
 
+    if let Some(severity) = matches.get_one::<String>("check-web-anchors") {
+        opt.check_web_anchors = Some(severity.parse().expect("invalid severity"));
+    }
+
+    if let Some(ignore_anchors) = matches.get_many::<String>("ignore-anchors") {
+        opt.ignore_anchors = Some(ignore_anchors.map(|x| x.to_string()).collect());
+    }
+
+    if let Some(slug_style) = matches.get_one::<String>("slug-style") {
+        opt.slug_style = Some(slug_style.parse().expect("invalid slug style"));
+    }
+
+    if matches.get_flag("autolink-literals") {
+        opt.autolink_literals = Some(true);
+    }
+
+    if let Some(languages) = matches.get_many::<String>("code-block-languages") {
+        opt.code_block_languages = Some(languages.map(|x| x.to_string()).collect());
+    }
+
+    if let Some(severity) = matches.get_one::<String>("code-block-severity") {
+        opt.code_block_severity = Some(severity.parse().expect("invalid severity"));
+    }
+
+    if let Some(keys) = matches.get_many::<String>("front-matter-keys") {
+        opt.front_matter_keys = Some(keys.map(|x| x.to_string()).collect());
+    }
+
+    if let Some(resolution) = matches.get_one::<String>("wiki-links") {
+        opt.wiki_links = Some(resolution.parse().expect("invalid wiki link resolution"));
+    }
+
+    if let Some(rules) = matches.get_many::<String>("structured-data") {
+        opt.structured_data = Some(
+            rules
+                .map(|rule| rule.parse().expect("invalid structured data rule"))
+                .collect(),
+        );
+    }
+
+    if let Some(retries) = matches.get_one::<String>("retries") {
+        opt.retries = Some(retries.parse().expect("invalid number of retries"));
+    }
+
+    if let Some(delay) = matches.get_one::<String>("retry-delay") {
+        opt.retry_delay = Some(delay.parse().expect("invalid retry delay"));
+    }
+
+    if let Some(max_wait) = matches.get_one::<String>("retry-max-wait") {
+        opt.retry_max_wait = Some(max_wait.parse().expect("invalid retry max wait"));
+    }
+
+    if let Some(timeout) = matches.get_one::<String>("connect-timeout") {
+        opt.connect_timeout = Some(timeout.parse().expect("invalid connect timeout"));
+    }
+
+    if let Some(timeout) = matches.get_one::<String>("read-timeout") {
+        opt.read_timeout = Some(timeout.parse().expect("invalid read timeout"));
+    }
+
+    if let Some(timeout) = matches.get_one::<String>("timeout") {
+        opt.timeout = Some(timeout.parse().expect("invalid timeout"));
+    }
+
+    if let Some(severity) = matches.get_one::<String>("timeout-severity") {
+        opt.timeout_severity = Some(severity.parse().expect("invalid severity"));
+    }
+
+    if let Some(accept_status) = matches.get_many::<String>("accept-status") {
+        opt.accept_status = Some(
+            accept_status
+                .map(|status| status.parse().expect("invalid status code range"))
+                .collect(),
+        );
+    }
+
+    if let Some(status_map) = matches.get_many::<String>("status-map") {
+        opt.status_map = Some(
+            status_map
+                .map(|mapping| mapping.parse().expect("invalid status mapping"))
+                .collect(),
+        );
+    }
+
     if let Some(root_dir) = matches.get_one::<String>("root-dir") {
         let root_path = Path::new(&normalize_path_separators(root_dir)).to_path_buf();
         if !root_path.is_dir() {
@@ -6,10 +6,13 @@ extern crate clap;
 extern crate lazy_static;
 
 use crate::link_extractors::link_extractor::MarkupLink;
-use crate::link_validator::link_type::get_link_type;
+use crate::link_validator::host::parse_http_headers;
 use crate::link_validator::link_type::LinkType;
 use crate::link_validator::resolve_target_link;
+use crate::link_validator::wiki_link::WikiIndex;
 use crate::markup::MarkupFile;
+use crate::markup::MarkupType;
+use crate::scheduler::Scheduler;
 use link_extractors::link_extractor::BrokenExtractedLink;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -22,14 +25,13 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
 use std::vec;
-use tokio::sync::Mutex;
-use tokio::time::{sleep_until, Duration, Instant};
 pub mod cli;
 pub mod file_traversal;
 pub mod link_extractors;
 pub mod link_validator;
 pub mod logger;
 pub mod markup;
+pub mod scheduler;
 pub use colored::*;
 pub use wildmatch::WildMatch;
 
@@ -37,8 +39,6 @@ use futures::{stream, StreamExt};
 use link_validator::LinkCheckResult;
 use url::Url;
 
-const PARALLEL_REQUESTS: usize = 20;
-
 #[derive(Default, Debug, Deserialize)]
 pub struct OptionalConfig {
     pub debug: Option<bool>,
@@ -66,6 +66,46 @@ pub struct OptionalConfig {
     pub files: Option<Vec<PathBuf>>,
     #[serde(rename(deserialize = "http-headers"))]
     pub http_headers: Option<Vec<String>>,
+    #[serde(rename(deserialize = "check-web-anchors"))]
+    pub check_web_anchors: Option<link_validator::Severity>,
+    #[serde(rename(deserialize = "ignore-anchors"))]
+    pub ignore_anchors: Option<Vec<String>>,
+    #[serde(rename(deserialize = "slug-style"))]
+    pub slug_style: Option<link_extractors::slug::SlugStyle>,
+    #[serde(rename(deserialize = "autolink-literals"))]
+    pub autolink_literals: Option<bool>,
+    #[serde(rename(deserialize = "code-block-languages"))]
+    pub code_block_languages: Option<Vec<String>>,
+    #[serde(rename(deserialize = "code-block-severity"))]
+    pub code_block_severity: Option<link_validator::Severity>,
+    #[serde(rename(deserialize = "front-matter-keys"))]
+    pub front_matter_keys: Option<Vec<String>>,
+    #[serde(rename(deserialize = "wiki-links"))]
+    pub wiki_links: Option<link_validator::wiki_link::WikiLinkResolution>,
+    #[serde(rename(deserialize = "structured-data"))]
+    pub structured_data:
+        Option<Vec<link_extractors::structured_data_link_extractor::StructuredDataRule>>,
+    pub retries: Option<u32>,
+    #[serde(rename(deserialize = "retry-delay"))]
+    pub retry_delay: Option<u64>,
+    #[serde(rename(deserialize = "retry-max-wait"))]
+    pub retry_max_wait: Option<u64>,
+    #[serde(rename(deserialize = "connect-timeout"))]
+    pub connect_timeout: Option<u64>,
+    #[serde(rename(deserialize = "read-timeout"))]
+    pub read_timeout: Option<u64>,
+    pub timeout: Option<u64>,
+    #[serde(rename(deserialize = "timeout-severity"))]
+    pub timeout_severity: Option<link_validator::Severity>,
+    #[serde(rename(deserialize = "max-concurrency"))]
+    pub max_concurrency: Option<usize>,
+    #[serde(rename(deserialize = "max-concurrency-per-host"))]
+    pub max_concurrency_per_host: Option<usize>,
+    #[serde(rename(deserialize = "accept-status"))]
+    pub accept_status: Option<Vec<link_validator::status::StatusRange>>,
+    #[serde(rename(deserialize = "status-map"))]
+    pub status_map: Option<Vec<link_validator::status::StatusMapping>>,
+    pub hosts: Option<Vec<link_validator::host::HostConfig>>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -121,7 +161,28 @@ IgnorePath: {:?}
 Throttle: {} ms
 CSVFile: {:?}
 Files: {:?}
-HttpHeaders: {:?}",
+HttpHeaders: {:?}
+CheckWebAnchors: {:?}
+IgnoreAnchors: {:?}
+SlugStyle: {:?}
+AutolinkLiterals: {}
+CodeBlockLanguages: {:?}
+CodeBlockSeverity: {:?}
+FrontMatterKeys: {:?}
+WikiLinks: {:?}
+StructuredData: {:?}
+Retries: {}
+RetryDelay: {:?} ms
+RetryMaxWait: {:?} ms
+ConnectTimeout: {:?} ms
+ReadTimeout: {:?} ms
+Timeout: {:?} ms
+TimeoutSeverity: {:?}
+MaxConcurrency: {}
+MaxConcurrencyPerHost: {:?}
+AcceptStatus: {:?}
+StatusMap: {:?}
+Hosts: {:?}",
             self.optional.debug.unwrap_or(false),
             self.directory.to_str().unwrap_or_default(),
             self.optional.do_not_warn_for_redirect_to,
@@ -136,7 +197,30 @@ HttpHeaders: {:?}",
             self.optional.throttle.unwrap_or(0),
             csv_file_str,
             files_str,
-            http_headers_str
+            http_headers_str,
+            self.optional.check_web_anchors,
+            self.optional.ignore_anchors,
+            self.optional.slug_style.unwrap_or_default(),
+            self.optional.autolink_literals.unwrap_or_default(),
+            self.optional.code_block_languages,
+            self.optional.code_block_severity,
+            self.optional.front_matter_keys,
+            self.optional.wiki_links,
+            self.optional.structured_data,
+            self.optional.retries.unwrap_or_default(),
+            self.optional.retry_delay,
+            self.optional.retry_max_wait,
+            self.optional.connect_timeout,
+            self.optional.read_timeout,
+            self.optional.timeout,
+            self.optional.timeout_severity,
+            self.optional
+                .max_concurrency
+                .unwrap_or(scheduler::DEFAULT_MAX_CONCURRENCY),
+            self.optional.max_concurrency_per_host,
+            self.optional.accept_status,
+            self.optional.status_map,
+            self.optional.hosts
         )
     }
 }
@@ -145,12 +229,16 @@ HttpHeaders: {:?}",
 struct FinalResult {
     target: Target,
     result_code: LinkCheckResult,
+    /// Status code of the response to a web link
+    status: Option<u16>,
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 struct Target {
     target: String,
     link_type: LinkType,
+    /// Links of code blocks are checked separately to report them with their own severity
+    from_code: bool,
 }
 
 fn find_all_links(config: &Config) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
@@ -158,7 +246,9 @@ fn find_all_links(config: &Config) -> Vec<Result<MarkupLink, BrokenExtractedLink
     file_traversal::find(config, &mut files);
     let mut links = vec![];
     for file in files {
-        links.append(&mut link_extractors::link_extractor::find_links(&file));
+        links.append(&mut link_extractors::link_extractor::find_links(
+            &file, config,
+        ));
     }
     links
 }
@@ -222,7 +312,7 @@ fn find_git_ignored_files(config: &Config) -> Option<Vec<PathBuf>> {
         let ignored_files = String::from_utf8(output.stdout)
             .ok()?
             .lines()
-            .filter(|line| line.ends_with(".md") || line.ends_with(".html"))
+            .filter(|line| MarkupType::from_path(Path::new(line.trim())).is_some())
             .filter_map(|line| {
                 let rel = line.trim();
                 let full = repo_root.join(rel);
@@ -255,7 +345,7 @@ fn find_git_untracked_files(config: &Config) -> Option<Vec<PathBuf>> {
         let untracked_files = String::from_utf8(output.stdout)
             .ok()?
             .lines()
-            .filter(|line| line.ends_with(".md") || line.ends_with(".html"))
+            .filter(|line| MarkupType::from_path(Path::new(line.trim())).is_some())
             .filter_map(|line| {
                 let rel = line.trim();
                 let full = repo_root.join(rel);
@@ -289,20 +379,45 @@ fn print_helper(
     }
 }
 
+/// Prefix the message with the status code of the response, unless the
+/// message already starts with it
+fn with_status(msg: &str, status: Option<u16>) -> String {
+    match status {
+        Some(status) if msg.is_empty() => status.to_string(),
+        Some(status) if !msg.starts_with(&format!("{status} ")) => format!("{status} - {msg}"),
+        _ => msg.to_string(),
+    }
+}
+
+/// Status code column of the CSV file, empty for links without response
+fn csv_status(status: Option<u16>) -> String {
+    status.map(|status| status.to_string()).unwrap_or_default()
+}
+
 fn print_result(result: &FinalResult, map: &HashMap<Target, Vec<MarkupLink>>) {
     for link in &map[&result.target] {
         match &result.result_code {
             LinkCheckResult::Ok => {
-                print_helper(link, &"OK".green(), "", false);
+                print_helper(link, &"OK".green(), &with_status("", result.status), false);
             }
             LinkCheckResult::NotImplemented(msg) | LinkCheckResult::Warning(msg) => {
-                print_helper(link, &"Warn".yellow(), msg, false);
+                print_helper(
+                    link,
+                    &"Warn".yellow(),
+                    &with_status(msg, result.status),
+                    false,
+                );
             }
             LinkCheckResult::Ignored(msg) => {
-                print_helper(link, &"Skip".green(), msg, false);
+                print_helper(
+                    link,
+                    &"Skip".green(),
+                    &with_status(msg, result.status),
+                    false,
+                );
             }
             LinkCheckResult::Failed(msg) => {
-                print_helper(link, &"Err".red(), msg, true);
+                print_helper(link, &"Err".red(), &with_status(msg, result.status), true);
             }
         }
     }
@@ -339,6 +454,13 @@ pub async fn run(config: &Config) -> Result<(), ()> {
 
     let is_gituntracked_enabled = gituntracked_files.is_some();
 
+    let wiki_index = config
+        .optional
+        .wiki_links
+        .map(|resolution| WikiIndex::new(config, resolution));
+    // Results known before checking, like of wiki links without target file
+    let mut known_results: HashMap<Target, LinkCheckResult> = HashMap::new();
+
     let mut broken_references: Vec<BrokenExtractedLink> = vec![];
     for link in &links {
         match link {
@@ -395,9 +517,35 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                     continue;
                 }
 
-                let link_type = get_link_type(&link.target);
-                let target = resolve_target_link(link, &link_type, config).await;
-                let t = Target { target, link_type };
+                let t = match &wiki_index {
+                    Some(wiki_index) if link.wiki_link => {
+                        let slug_style = config.optional.slug_style.unwrap_or_default();
+                        match wiki_index.resolve(&link.source, &link.target, slug_style) {
+                            Ok(target) => Target {
+                                target,
+                                link_type: LinkType::FileSystem,
+                                from_code: link.from_code,
+                            },
+                            Err(msg) => {
+                                let t = Target {
+                                    target: link.target.clone(),
+                                    link_type: LinkType::Unknown,
+                                    from_code: link.from_code,
+                                };
+                                known_results.insert(t.clone(), LinkCheckResult::Failed(msg));
+                                t
+                            }
+                        }
+                    }
+                    _ => {
+                        let (target, link_type) = resolve_target_link(link, config).await;
+                        Target {
+                            target,
+                            link_type,
+                            from_code: link.from_code,
+                        }
+                    }
+                };
                 match link_target_groups.get_mut(&t) {
                     Some(v) => v.push(link.clone()),
                     None => {
@@ -419,81 +567,39 @@ pub async fn run(config: &Config) -> Result<(), ()> {
 
     // Parse HTTP headers from config
     let http_headers: Arc<Vec<(String, String)>> = Arc::new(match &config.optional.http_headers {
-        Some(headers) => headers
-            .iter()
-            .filter_map(|h| {
-                let parts: Vec<&str> = h.splitn(2, ':').collect();
-                if parts.len() == 2 {
-                    Some((parts[0].trim().to_string(), parts[1].trim().to_string()))
-                } else {
-                    warn!("Invalid HTTP header format (expected 'Name: Value'): {}", h);
-                    None
-                }
-            })
-            .collect(),
+        Some(headers) => parse_http_headers(headers),
         None => vec![],
     });
     info!("Custom HTTP headers: {:?}", http_headers);
 
-    let throttle = config.optional.throttle.unwrap_or_default() > 0;
-    info!("Throttle HTTP requests to same host: {throttle:?}");
-    let waits = Arc::new(Mutex::new(HashMap::new()));
+    let scheduler = Scheduler::new(&config.optional);
+    let max_concurrency = scheduler.max_concurrency();
+    let known_results = &known_results;
+    let scheduler = &scheduler;
     // See also http://patshaughnessy.net/2020/1/20/downloading-100000-files-using-async-rust
+    // At most `max-concurrency` checks are polled at the same time. The
+    // scheduler applies the limits and the throttle of the hosts.
     let mut buffered_stream = stream::iter(link_target_groups.keys())
         .map(|target| {
-            let waits = waits.clone();
             let do_not_warn_for_redirect_to = Arc::clone(&do_not_warn_for_redirect_to);
             let http_headers = Arc::clone(&http_headers);
             async move {
-                if throttle && target.link_type == LinkType::Http {
-                    let parsed = match Url::parse(&target.target) {
-                        Ok(parsed) => parsed,
-                        Err(error) => {
-                            return FinalResult {
-                                target: target.clone(),
-                                result_code: LinkCheckResult::Failed(format!(
-                                    "Could not parse URL type. Err: {error:?}"
-                                )),
-                            }
-                        }
-                    };
-                    let host = match parsed.host_str() {
-                        Some(host) => host.to_string(),
-                        None => {
-                            return FinalResult {
-                                target: target.clone(),
-                                result_code: LinkCheckResult::Failed(
-                                    "Failed to determine host".to_string(),
-                                ),
-                            }
-                        }
-                    };
-                    let mut waits = waits.lock().await;
-
-                    let mut wait_until: Option<Instant> = None;
-                    let next_wait = match waits.get(&host) {
-                        Some(old) => {
-                            wait_until = Some(*old);
-                            *old + Duration::from_millis(
-                                config.optional.throttle.unwrap_or_default().into(),
-                            )
-                        }
-                        None => {
-                            Instant::now()
-                                + Duration::from_millis(
-                                    config.optional.throttle.unwrap_or_default().into(),
-                                )
-                        }
+                if let Some(result_code) = known_results.get(target) {
+                    return FinalResult {
+                        target: target.clone(),
+                        result_code: result_code.clone(),
+                        status: None,
                     };
-                    waits.insert(host, next_wait);
-                    drop(waits);
-
-                    if let Some(deadline) = wait_until {
-                        sleep_until(deadline).await;
-                    }
                 }
+                let host = match target.link_type {
+                    LinkType::Http => Url::parse(&target.target)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string)),
+                    _ => None,
+                };
+                let _permit = scheduler.acquire(host.as_deref()).await;
 
-                let result_code = link_validator::check(
+                let (result_code, status) = link_validator::check(
                     &target.target,
                     &target.link_type,
                     config,
@@ -501,14 +607,21 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                     &http_headers,
                 )
                 .await;
+                let result_code = match (result_code, config.optional.code_block_severity) {
+                    (LinkCheckResult::Failed(msg), Some(severity)) if target.from_code => {
+                        severity.to_result(msg)
+                    }
+                    (result_code, _) => result_code,
+                };
 
                 FinalResult {
                     target: target.clone(),
                     result_code,
+                    status,
                 }
             }
         })
-        .buffer_unordered(PARALLEL_REQUESTS);
+        .buffer_unordered(max_concurrency);
 
     let mut oks = 0;
     let mut warnings = 0;
@@ -587,7 +700,7 @@ pub async fn run(config: &Config) -> Result<(), ()> {
     let mut csv_file = if let Some(csv_path) = &config.optional.csv_file {
         info!("Write CSV file: {}", csv_path.display());
         let mut file = fs::File::create(csv_path).unwrap();
-        writeln!(file, "source,line,column,target,severity").unwrap();
+        writeln!(file, "source,line,column,target,severity,status").unwrap();
         Some(file)
     } else {
         None
@@ -601,8 +714,12 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                 for link in &link_target_groups[&res.target] {
                     writeln!(
                         file,
-                        "{},{},{},{},WARN",
-                        link.source, link.line, link.column, link.target
+                        "{},{},{},{},WARN,{}",
+                        link.source,
+                        link.line,
+                        link.column,
+                        link.target,
+                        csv_status(res.status)
                     )
                     .unwrap();
                 }
@@ -611,7 +728,7 @@ pub async fn run(config: &Config) -> Result<(), ()> {
             for broken_ref in &broken_references {
                 writeln!(
                     file,
-                    "{},{},{},{},WARN",
+                    "{},{},{},{},WARN,",
                     broken_ref.source, broken_ref.line, broken_ref.column, broken_ref.reference
                 )
                 .unwrap();
@@ -633,8 +750,12 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                 if let Some(ref mut file) = csv_file {
                     writeln!(
                         file,
-                        "{},{},{},{},ERR",
-                        link.source, link.line, link.column, link.target
+                        "{},{},{},{},ERR,{}",
+                        link.source,
+                        link.line,
+                        link.column,
+                        link.target,
+                        csv_status(res.status)
                     )
                     .unwrap();
                 }
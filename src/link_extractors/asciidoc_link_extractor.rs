@@ -0,0 +1,609 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_validator::link_type::get_link_type;
+use crate::link_validator::link_type::LinkType;
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::link_extractor::BrokenExtractedLink;
+use super::slug::SlugStyle;
+
+/// Link extractor for AsciiDoc documents.
+///
+/// Supports the `link:`, `xref:`, `mailto:` and `image:` inline macros,
+/// `<<id>>` cross references, the `image::` and `include::` block macros and
+/// bare URLs. Attribute references like `{docs-url}` are replaced with the
+/// values of attribute entries defined in the same document.
+pub struct AsciiDocLinkExtractor();
+
+/// Delimiters of blocks whose content is not scanned for links
+const SKIPPED_BLOCK_DELIMITERS: [char; 4] = ['-', '.', '+', '/'];
+
+impl LinkExtractor for AsciiDocLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        lazy_static! {
+            static ref BLOCK_MACRO: Regex =
+                Regex::new(r"^(image|include)::([^\[\s][^\[]*)\[.*\]\s*$").unwrap();
+        }
+
+        let mut document = Document::default();
+        for (line_num, line) in Lines::new(text) {
+            if let Some(attribute) = document.attribute_entry(line) {
+                debug!("AsciiDoc attribute '{attribute}' in line {line_num}");
+                continue;
+            }
+            if let Some(block_macro) = BLOCK_MACRO.captures(line) {
+                let target = block_macro.get(2).unwrap();
+                let target = match &block_macro[1] {
+                    "image" => document.image_target(target.as_str()),
+                    _ => document.substitute_attributes(target.as_str()),
+                };
+                if let Some(target) = target {
+                    document.push(line, line_num, 0, target);
+                }
+                continue;
+            }
+            find_inline(&mut document, line, line_num);
+        }
+
+        document.links.into_iter().map(Ok).collect()
+    }
+
+    /// Ids of sections, block anchors and inline anchors. Section ids are
+    /// generated the way Asciidoctor does it, the slug style is not used.
+    fn find_anchors(&self, text: &str, _slug_style: SlugStyle) -> Option<Vec<String>> {
+        lazy_static! {
+            static ref SECTION_TITLE: Regex = Regex::new(r"^(?:=+|#+)\s+(\S.*?)\s*$").unwrap();
+            static ref BLOCK_ATTRIBUTES: Regex = Regex::new(r"^\[([^\[\]]*)\]\s*$").unwrap();
+            static ref BLOCK_ANCHOR: Regex =
+                Regex::new(r"^\[\[([[:alpha:]_:][[:word:]:.-]*)(?:,[^\]]*)?\]\]\s*$").unwrap();
+            static ref INLINE_ANCHOR: Regex = Regex::new(
+                r"\[\[\[?([[:alpha:]_:][[:word:]:.-]*)(?:,[^\]]*)?\]\]|anchor:([[:alpha:]_:][[:word:]:.-]*)\[|\[#([[:alpha:]_:][[:word:]:.-]*)[^\]]*\]#"
+            )
+            .unwrap();
+            static ref ID_ATTRIBUTE: Regex =
+                Regex::new(r#"(?:^|,)\s*id\s*=\s*"?([^",\s]+)"?"#).unwrap();
+        }
+
+        let mut document = Document::default();
+        let mut anchors: Vec<String> = vec![];
+        let add = |anchors: &mut Vec<String>, id: String| {
+            if !anchors.contains(&id) {
+                anchors.push(id);
+            }
+        };
+        // Explicit id of the next block or section
+        let mut block_id: Option<String> = None;
+
+        for (_, line) in Lines::new(text) {
+            if document.attribute_entry(line).is_some() {
+                continue;
+            }
+            if let Some(anchor) = BLOCK_ANCHOR.captures(line) {
+                block_id = Some(anchor[1].to_string());
+                add(&mut anchors, anchor[1].to_string());
+                continue;
+            }
+            if let Some(attributes) = BLOCK_ATTRIBUTES.captures(line) {
+                if let Some(id) = block_attributes_id(&attributes[1])
+                    .or_else(|| ID_ATTRIBUTE.captures(&attributes[1]).map(|c| c[1].into()))
+                {
+                    block_id = Some(id.clone());
+                    add(&mut anchors, id);
+                }
+                continue;
+            }
+            if let Some(title) = SECTION_TITLE.captures(line) {
+                if block_id.take().is_none() && document.section_ids() {
+                    let id = document.section_id(&title[1], &anchors);
+                    add(&mut anchors, id);
+                }
+            } else {
+                block_id = None;
+            }
+            for anchor in INLINE_ANCHOR.captures_iter(line) {
+                let id = anchor
+                    .get(1)
+                    .or_else(|| anchor.get(2))
+                    .or_else(|| anchor.get(3))
+                    .unwrap();
+                add(&mut anchors, id.as_str().to_string());
+            }
+        }
+        Some(anchors)
+    }
+}
+
+/// Id set with the shorthand `[#id]` or `[role#id.class]` in the first
+/// positional attribute of a block attribute list
+fn block_attributes_id(attributes: &str) -> Option<String> {
+    let first = attributes.split(',').next()?;
+    let start = first.find('#')? + 1;
+    let id: String = first[start..]
+        .chars()
+        .take_while(|c| !matches!(c, '.' | '%' | '#') && !c.is_whitespace())
+        .collect();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+/// Iterator over the lines of a document which may contain links. Skips
+/// comments, listing, literal and passthrough blocks and literal paragraphs.
+struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    delimiter: Option<&'a str>,
+    literal_paragraph: bool,
+    paragraph_start: bool,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines().enumerate(),
+            delimiter: None,
+            literal_paragraph: false,
+            paragraph_start: true,
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    /// Line number (1-indexed) and content of the line
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (idx, line) in self.lines.by_ref() {
+            let trimmed = line.trim_end();
+            if let Some(delimiter) = self.delimiter {
+                if trimmed == delimiter {
+                    self.delimiter = None;
+                }
+                continue;
+            }
+            if is_skipped_block_delimiter(trimmed) {
+                self.delimiter = Some(trimmed);
+                continue;
+            }
+            if trimmed.is_empty() {
+                self.literal_paragraph = false;
+                self.paragraph_start = true;
+                continue;
+            }
+            if self.literal_paragraph {
+                continue;
+            }
+            let paragraph_start = self.paragraph_start;
+            self.paragraph_start = false;
+            if paragraph_start
+                && line.starts_with(char::is_whitespace)
+                && !is_list_item(line.trim_start())
+            {
+                self.literal_paragraph = true;
+                continue;
+            }
+            if line.starts_with("//") {
+                continue;
+            }
+            return Some((idx + 1, line));
+        }
+        None
+    }
+}
+
+/// Delimiter line of a listing (`----`), literal (`....`), passthrough
+/// (`++++`) or comment (`////`) block
+fn is_skipped_block_delimiter(line: &str) -> bool {
+    match line.chars().next() {
+        Some(first) => {
+            SKIPPED_BLOCK_DELIMITERS.contains(&first)
+                && line.chars().count() >= 4
+                && line.chars().all(|c| c == first)
+        }
+        None => false,
+    }
+}
+
+fn is_list_item(line: &str) -> bool {
+    lazy_static! {
+        static ref LIST_ITEM: Regex =
+            Regex::new(r"^(?:[*.-]+|\d+\.|[a-zA-Z]\.|<\d+>)\s+\S").unwrap();
+    }
+    LIST_ITEM.is_match(line)
+}
+
+#[derive(Default)]
+struct Document {
+    links: Vec<MarkupLink>,
+    attributes: HashMap<String, Option<String>>,
+}
+
+impl Document {
+    /// Handle an attribute entry like `:name: value` or `:name!:`. Returns the
+    /// name of the attribute.
+    fn attribute_entry(&mut self, line: &str) -> Option<String> {
+        lazy_static! {
+            static ref ATTRIBUTE_ENTRY: Regex =
+                Regex::new(r"^:(!?)([[:word:]][[:word:]-]*)(!?):(?:\s+(.*?))?\s*$").unwrap();
+        }
+        let entry = ATTRIBUTE_ENTRY.captures(line)?;
+        let name = entry[2].to_lowercase();
+        let unset = !entry[1].is_empty() || !entry[3].is_empty();
+        let value = if unset {
+            None
+        } else {
+            let value = entry.get(4).map_or("", |v| v.as_str());
+            Some(self.substitute_attributes(value).unwrap_or_default())
+        };
+        self.attributes.insert(name.clone(), value);
+        Some(name)
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name)?.as_deref()
+    }
+
+    /// Replace attribute references like `{name}`. Returns `None` if an
+    /// attribute is not defined in this document.
+    fn substitute_attributes(&self, text: &str) -> Option<String> {
+        lazy_static! {
+            static ref ATTRIBUTE_REFERENCE: Regex =
+                Regex::new(r"\{([[:word:]][[:word:]-]*)\}").unwrap();
+        }
+        let mut result = String::new();
+        let mut last = 0;
+        for reference in ATTRIBUTE_REFERENCE.captures_iter(text) {
+            let whole = reference.get(0).unwrap();
+            match self.attribute(&reference[1].to_lowercase()) {
+                Some(value) => {
+                    result.push_str(&text[last..whole.start()]);
+                    result.push_str(value);
+                    last = whole.end();
+                }
+                None => {
+                    debug!("Unknown AsciiDoc attribute in '{text}'. Skip check.");
+                    return None;
+                }
+            }
+        }
+        result.push_str(&text[last..]);
+        Some(result)
+    }
+
+    /// Relative image paths are resolved against the `imagesdir` attribute
+    fn image_target(&self, target: &str) -> Option<String> {
+        let target = self.substitute_attributes(target)?;
+        if target.contains('$') {
+            debug!("Skip check of Antora resource id '{target}'.");
+            return None;
+        }
+        match self.attribute("imagesdir") {
+            Some(dir)
+                if !dir.is_empty()
+                    && get_link_type(&target) == LinkType::FileSystem
+                    && !target.starts_with(['/', '\\']) =>
+            {
+                Some(format!("{}/{}", dir.trim_end_matches('/'), target))
+            }
+            _ => Some(target),
+        }
+    }
+
+    /// Cross references point to an id in the same document (`id`) or to
+    /// another document (`other.adoc#id`, `other#id`, `other.adoc`)
+    fn xref_target(&self, target: &str) -> Option<String> {
+        let target = self.substitute_attributes(target.trim())?;
+        if target.chars().any(char::is_whitespace) {
+            debug!("Skip check of cross reference to section title '{target}'.");
+            return None;
+        }
+        if target.contains('$') || target.contains(':') {
+            debug!("Skip check of Antora resource id '{target}'.");
+            return None;
+        }
+        let (path, fragment) = match target.find('#') {
+            Some(idx) => (&target[..idx], Some(&target[idx + 1..])),
+            None if target.ends_with(".adoc") => (target.as_str(), None),
+            None => ("", Some(target.as_str())),
+        };
+        let path = if path.is_empty() || path.contains('.') {
+            path.to_string()
+        } else {
+            format!("{path}.adoc")
+        };
+        Some(match fragment {
+            Some(fragment) => format!("{path}#{fragment}"),
+            None => path,
+        })
+    }
+
+    fn section_ids(&self) -> bool {
+        !matches!(self.attributes.get("sectids"), Some(None))
+    }
+
+    /// Id which Asciidoctor generates for a section title
+    fn section_id(&self, title: &str, known: &[String]) -> String {
+        lazy_static! {
+            static ref INVALID_ID_CHARS: Regex = Regex::new(
+                r"<[^>]+>|&(?:[a-z][a-z]+\d{0,2}|#\d\d\d{0,4}|#x[\da-f][\da-f][\da-f]{0,3});|[^ [[:word:]]\-.]+"
+            )
+            .unwrap();
+        }
+        let prefix = match self.attributes.get("idprefix") {
+            Some(prefix) => prefix.clone().unwrap_or_default(),
+            None => "_".to_string(),
+        };
+        let separator = match self.attributes.get("idseparator") {
+            Some(separator) => separator
+                .as_deref()
+                .and_then(|s| s.chars().next())
+                .map(String::from)
+                .unwrap_or_default(),
+            None => "_".to_string(),
+        };
+        let title = title.to_lowercase();
+        let title = INVALID_ID_CHARS.replace_all(&title, "");
+        let mut id = String::new();
+        if separator.is_empty() {
+            id = format!("{prefix}{}", title.replace(' ', ""));
+        } else {
+            // Replace each run of spaces, periods and hyphens with one separator
+            let mut last_replaced = false;
+            for c in prefix.chars().chain(title.chars()) {
+                if matches!(c, ' ' | '.' | '-') || separator.starts_with(c) {
+                    if !last_replaced {
+                        id.push_str(&separator);
+                    }
+                    last_replaced = true;
+                } else {
+                    id.push(c);
+                    last_replaced = false;
+                }
+            }
+            if id.ends_with(separator.as_str()) {
+                id.truncate(id.len() - separator.len());
+            }
+            if prefix.is_empty() && id.starts_with(separator.as_str()) {
+                id.drain(..separator.len());
+            }
+        }
+        if !known.contains(&id) {
+            return id;
+        }
+        (2..)
+            .map(|count| format!("{id}{separator}{count}"))
+            .find(|candidate| !known.contains(candidate))
+            .unwrap()
+    }
+
+    /// Add a link found at byte `byte_idx` of the line
+    fn push(&mut self, line: &str, line_num: usize, byte_idx: usize, target: String) {
+        self.links.push(MarkupLink {
+            source: String::new(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+            target,
+            line: line_num,
+            column: line[..byte_idx].chars().count() + 1,
+        });
+    }
+}
+
+/// Find inline macros, cross references and bare URLs
+fn find_inline(document: &mut Document, line: &str, line_num: usize) {
+    lazy_static! {
+        static ref PASSTHROUGH: Regex = Regex::new(
+            r"\+\+\+.+?\+\+\+|\+\+.+?\+\+|pass:[a-z,]*\[[^\]]*\]|(?:^|\s)\+[^\s+](?:[^+]*[^\s+])?\+"
+        )
+        .unwrap();
+        static ref CROSS_REFERENCE: Regex = Regex::new(r"<<([^<>,]+)(?:,[^>]*)?>>").unwrap();
+        static ref INLINE_MACRO: Regex =
+            Regex::new(r"(?:^|[^[[:word:]]\\])(link|xref|image|mailto):([^\s\[:][^\s\[]*)\[")
+                .unwrap();
+        static ref URL: Regex =
+            Regex::new(r#"(?:^|[^[[:word:]]\\/:"'=])((?:https?|ftp|irc)://[^\s\[\]<>"']+)"#)
+                .unwrap();
+        static ref ATTRIBUTE_URL: Regex =
+            Regex::new(r#"(?:^|[^[[:word:]]\\])(\{[[:word:]][[:word:]-]*\}[^\s\[\]<>"']*)"#)
+                .unwrap();
+    }
+
+    // Blank processed parts of the line to not match them twice
+    let mut text = line.to_string();
+    let blank = |text: &mut String, range: std::ops::Range<usize>| {
+        text.replace_range(range.clone(), &" ".repeat(range.len()));
+    };
+
+    for passthrough in PASSTHROUGH.find_iter(&text.clone()) {
+        blank(&mut text, passthrough.range());
+    }
+
+    let mut links: Vec<(usize, String)> = vec![];
+    for xref in CROSS_REFERENCE.captures_iter(&text.clone()) {
+        let whole = xref.get(0).unwrap();
+        if let Some(target) = document.xref_target(&xref[1]) {
+            links.push((whole.start(), target));
+        }
+        blank(&mut text, whole.range());
+    }
+
+    for inline_macro in INLINE_MACRO.captures_iter(&text.clone()) {
+        let name = inline_macro.get(1).unwrap();
+        let target = inline_macro.get(2).unwrap().as_str();
+        let target = match name.as_str() {
+            "link" => document.substitute_attributes(target),
+            "xref" => document.xref_target(target),
+            "image" => document.image_target(target),
+            _ => Some(format!("mailto:{target}")),
+        };
+        if let Some(target) = target {
+            links.push((name.start(), target));
+        }
+        blank(&mut text, name.start()..inline_macro.get(2).unwrap().end());
+    }
+
+    for url in URL.captures_iter(&text.clone()) {
+        let url = url.get(1).unwrap();
+        let target = url
+            .as_str()
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+        if let Some(target) = document.substitute_attributes(target) {
+            links.push((url.start(), target));
+        }
+    }
+
+    // URLs defined by attributes like `{url-docs}/guide[Guide]`
+    for reference in ATTRIBUTE_URL.captures_iter(&text) {
+        let reference = reference.get(1).unwrap();
+        if let Some(target) = document.substitute_attributes(reference.as_str()) {
+            if matches!(get_link_type(&target), LinkType::Http | LinkType::Ftp) {
+                links.push((reference.start(), target));
+            }
+        }
+    }
+
+    links.sort_by_key(|(idx, _)| *idx);
+    for (idx, target) in links {
+        document.push(line, line_num, idx, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(target: &str, line: usize, column: usize) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+            target: target.to_string(),
+            line,
+            column,
+        })
+    }
+
+    #[test]
+    fn no_link() {
+        let le = AsciiDocLinkExtractor();
+        let input = "= Title\n\nThis is *no* link. And an [[anchor]] too.";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn link_macros_and_urls() {
+        let le = AsciiDocLinkExtractor();
+        let input = "See link:docs/guide.html[the guide] and https://example.com[Example].\nVisit https://example.org/path, or mailto:mail@example.com[mail].\nNot \\https://escaped.com.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("docs/guide.html", 1, 5),
+                link("https://example.com", 1, 41),
+                link("https://example.org/path", 2, 7),
+                link("mailto:mail@example.com", 2, 36),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn cross_references() {
+        let le = AsciiDocLinkExtractor();
+        let input = "See <<install>>, <<usage,Usage>> and <<other.adoc#api,API>>.\nAlso xref:guide.adoc[], xref:guide#setup[Setup] and xref:intro[].\nNatural <<Section Title>> and xref:module:page.adoc[] are skipped.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("#install", 1, 5),
+                link("#usage", 1, 18),
+                link("other.adoc#api", 1, 38),
+                link("guide.adoc", 2, 6),
+                link("guide.adoc#setup", 2, 25),
+                link("#intro", 2, 53),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn images_and_includes() {
+        let le = AsciiDocLinkExtractor();
+        let input = "include::partials/intro.adoc[leveloffset=+1]\n\nimage::logo.png[Logo]\n\n:imagesdir: assets/img\n\nAn icon image:icon.svg[Icon,16] and image:https://example.com/a.png[].\n\nimage::diagram.png[]";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("partials/intro.adoc", 1, 1),
+                link("logo.png", 3, 1),
+                link("assets/img/icon.svg", 7, 9),
+                link("https://example.com/a.png", 7, 37),
+                link("assets/img/diagram.png", 9, 1),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn attribute_references() {
+        let le = AsciiDocLinkExtractor();
+        let input = ":url-docs: https://docs.example.com\n:url-api: {url-docs}/api\n\nSee {url-api}[API] and link:{url-docs}/guide[guide].\nUnknown link:{unknown}/x[] is skipped.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://docs.example.com/api", 4, 5),
+                link("https://docs.example.com/guide", 4, 24),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn skipped_blocks() {
+        let le = AsciiDocLinkExtractor();
+        let input = "// https://comment.com\n////\nhttps://comment-block.com\n////\n[source]\n----\nhttps://listing.com\n----\n\n  https://literal-paragraph.com\n  continued\n\n* https://list.com\n\n....\nhttps://literal.com\n....\n++++\n<a href=\"https://pass.com\">\n++++\nSome +https://inline-pass.com+ text.";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("https://list.com", 13, 3)], result);
+    }
+
+    #[test]
+    fn unicode_columns() {
+        let le = AsciiDocLinkExtractor();
+        let input = "Über änderungen: https://example.com/ö";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("https://example.com/ö", 1, 18)], result);
+    }
+
+    #[test]
+    fn anchors() {
+        let le = AsciiDocLinkExtractor();
+        let input = "= Document Title\n\n== Getting Started\n\n[[custom]]\n== Ignored Auto Id\n\n[#short.role]\n== Another\n\n== Getting Started\n\nText with [[inline]] and anchor:macro[] and [#phrase]#text#.\n\n----\n== Not a Section\n----\n\n=== API & Usage: v1.2";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(
+            vec![
+                "_document_title",
+                "_getting_started",
+                "custom",
+                "short",
+                "_getting_started_2",
+                "inline",
+                "macro",
+                "phrase",
+                "_api_usage_v1_2",
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn anchors_with_id_attributes() {
+        let le = AsciiDocLinkExtractor();
+        let input = ":idprefix:\n:idseparator: -\n\n== Getting Started\n\n[quote,id=quoted]\nText\n\n:sectids!:\n\n== No Id";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["getting-started", "quoted"], result);
+    }
+}
@@ -0,0 +1,112 @@
+use super::structured_data::{toml_values, yaml_values, DataValue};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    Yaml,
+    Toml,
+}
+
+/// YAML (`---`) or TOML (`+++`) front matter at the start of a markdown file
+/// as used by Jekyll or Hugo
+#[derive(Debug, PartialEq, Eq)]
+pub struct FrontMatter<'a> {
+    format: Format,
+    /// Text in between the fences
+    content: &'a str,
+    /// Byte offset of the content
+    content_start: usize,
+    /// Byte offset after the closing fence
+    end: usize,
+}
+
+impl<'a> FrontMatter<'a> {
+    pub fn find(text: &'a str) -> Option<FrontMatter<'a>> {
+        let content_start = text.find('\n')? + 1;
+        let fence = text[..content_start].trim_end();
+        let format = match fence {
+            "---" => Format::Yaml,
+            "+++" => Format::Toml,
+            _ => return None,
+        };
+        let mut line_start = content_start;
+        for line in text[content_start..].split_inclusive('\n') {
+            let line_content = line.trim_end();
+            if line_content == fence || (format == Format::Yaml && line_content == "...") {
+                return Some(FrontMatter {
+                    format,
+                    content: &text[content_start..line_start],
+                    content_start,
+                    end: line_start + line.len(),
+                });
+            }
+            line_start += line.len();
+        }
+        None
+    }
+
+    /// Replace the front matter with whitespace. The line breaks are kept, so
+    /// that the positions of the remaining text do not change.
+    pub fn blank(&self, text: &str) -> String {
+        let mut blanked = String::with_capacity(text.len());
+        for c in text[..self.end].chars() {
+            match c {
+                '\n' => blanked.push('\n'),
+                c => blanked.extend(std::iter::repeat_n(' ', c.len_utf8())),
+            }
+        }
+        blanked.push_str(&text[self.end..]);
+        blanked
+    }
+
+    /// Values of the front matter with offsets relative to the whole text
+    pub fn values(&self) -> Vec<DataValue> {
+        let mut values = match self.format {
+            Format::Yaml => yaml_values(self.content),
+            Format::Toml => toml_values(self.content),
+        };
+        for value in &mut values {
+            value.offset += self.content_start;
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test]
+    fn yaml() {
+        let text = "---\ntitle: Ü\nimage: /a.png\n...\n# Heading";
+        let front_matter = FrontMatter::find(text).unwrap();
+        let blanked = front_matter.blank(text);
+        assert_eq!("   \n         \n             \n   \n# Heading", blanked);
+        assert_eq!(text.len(), blanked.len());
+        let values = front_matter.values();
+        assert_eq!("/a.png", values[1].value);
+        assert!(text[values[1].offset..].starts_with("/a.png"));
+    }
+
+    #[test]
+    fn toml() {
+        let text = "+++\r\ncanonical = \"https://example.com\"\r\n+++\r\nText";
+        let front_matter = FrontMatter::find(text).unwrap();
+        let blanked = front_matter.blank(text);
+        assert_eq!(text.len(), blanked.len());
+        assert_eq!(4, blanked.lines().count());
+        assert!(blanked.ends_with("   \nText"));
+        let values = front_matter.values();
+        assert_eq!(vec!["canonical"], values[0].path);
+        assert!(text[values[0].offset..].starts_with("https://example.com"));
+    }
+
+    #[test_case("Text\n---\nkey: value\n---\n")]
+    #[test_case(" ---\nkey: value\n---\n")]
+    #[test_case("---\nkey: value\n")]
+    #[test_case("+++\nkey = 'value'\n---\n")]
+    #[test_case("---")]
+    fn no_front_matter(text: &str) {
+        assert_eq!(None, FrontMatter::find(text));
+    }
+}
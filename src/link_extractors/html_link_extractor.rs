@@ -5,116 +5,582 @@ use crate::link_validator::link_type::LinkType;
 
 use super::ignore_comments::IgnoreRegions;
 use super::link_extractor::BrokenExtractedLink;
+use super::slug::SlugStyle;
+
+/// Link extractor for html documents.
+///
+/// The document is split into tags by a tokenizer which follows the html
+/// specification. Links are extracted from all attributes which contain URLs.
 pub struct HtmlLinkExtractor();
 
-#[derive(Clone, Copy, Debug)]
-enum ParserState {
-    Text,
-    Comment,
-    Anchor,
-    EqualSign,
-    Link,
-}
+/// Elements and their attributes which contain a single URL
+const URL_ATTRIBUTES: [(&str, &str); 22] = [
+    ("a", "href"),
+    ("area", "href"),
+    ("link", "href"),
+    ("img", "src"),
+    ("script", "src"),
+    ("iframe", "src"),
+    ("frame", "src"),
+    ("embed", "src"),
+    ("video", "src"),
+    ("video", "poster"),
+    ("audio", "src"),
+    ("source", "src"),
+    ("track", "src"),
+    ("input", "src"),
+    ("form", "action"),
+    ("button", "formaction"),
+    ("input", "formaction"),
+    ("object", "data"),
+    ("blockquote", "cite"),
+    ("q", "cite"),
+    ("del", "cite"),
+    ("ins", "cite"),
+];
+
+/// Elements with a `srcset` attribute containing a list of image candidates
+const SRCSET_ELEMENTS: [&str; 2] = ["img", "source"];
+
+/// Elements whose content is text and never contains tags
+const RAW_TEXT_ELEMENTS: [&str; 8] = [
+    "script", "style", "xmp", "iframe", "noembed", "noframes", "textarea", "title",
+];
 
 impl LinkExtractor for HtmlLinkExtractor {
     fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
-        let mut result: Vec<Result<MarkupLink, BrokenExtractedLink>> = Vec::new();
-        let mut state: ParserState = ParserState::Text;
-        let mut link_column = 0;
-        let mut link_line = 0;
         let ignore_regions = IgnoreRegions::from_text(text);
+        let positions = Positions::new(text);
+        let mut result: Vec<Result<MarkupLink, BrokenExtractedLink>> = Vec::new();
+        let base = find_base(text);
 
-        for (line, line_str) in text.lines().enumerate() {
-            let line_chars: Vec<char> = line_str.chars().collect();
-            let mut column: usize = 0;
-            while line_chars.get(column).is_some() {
-                match state {
-                    ParserState::Comment => {
-                        if line_chars.get(column) == Some(&'-')
-                            && line_chars.get(column + 1) == Some(&'-')
-                            && line_chars.get(column + 2) == Some(&'>')
-                        {
-                            column += 2;
-                            state = ParserState::Text;
-                        }
-                    }
-                    ParserState::Text => {
-                        link_column = column;
-                        link_line = line;
-                        if line_chars.get(column) == Some(&'<')
-                            && line_chars.get(column + 1) == Some(&'!')
-                            && line_chars.get(column + 2) == Some(&'-')
-                            && line_chars.get(column + 3) == Some(&'-')
-                        {
-                            column += 3;
-                            state = ParserState::Comment;
-                        } else if line_chars.get(column) == Some(&'<')
-                            && line_chars.get(column + 1) == Some(&'a')
-                        {
-                            column += 1;
-                            state = ParserState::Anchor;
-                        }
-                    }
-                    ParserState::Anchor => {
-                        if line_chars.get(column) == Some(&'h')
-                            && line_chars.get(column + 1) == Some(&'r')
-                            && line_chars.get(column + 2) == Some(&'e')
-                            && line_chars.get(column + 3) == Some(&'f')
-                        {
-                            column += 3;
-                            state = ParserState::EqualSign;
-                        }
-                    }
-                    ParserState::EqualSign => {
-                        match line_chars.get(column) {
-                            Some(x) if x.is_whitespace() => {}
-                            Some(x) if x == &'=' => state = ParserState::Link,
-                            Some(_) => state = ParserState::Anchor,
-                            None => {}
-                        };
+        for tag in Tokenizer::new(text) {
+            for (idx, mut link) in tag.links() {
+                let (line, column) = positions.line_column(idx);
+                if ignore_regions.is_line_ignored(line) {
+                    continue;
+                }
+                if get_link_type(&link) == LinkType::FileSystem {
+                    link = url_escape::decode(link.as_str()).to_string();
+                };
+                result.push(Ok(MarkupLink {
+                    column,
+                    line,
+                    target: link,
+                    source: "".to_string(),
+                    location: None,
+                    base: base.clone(),
+                    from_code: false,
+                    wiki_link: false,
+                }));
+            }
+        }
+        result
+    }
+
+    fn find_anchors(&self, text: &str, _slug_style: SlugStyle) -> Option<Vec<String>> {
+        let mut anchors = vec![];
+        for tag in Tokenizer::new(text) {
+            for attribute in &tag.attributes {
+                let is_anchor =
+                    attribute.name == "id" || (attribute.name == "name" && tag.name == "a");
+                if is_anchor && !attribute.value.is_empty() {
+                    anchors.push(attribute.value.clone());
+                }
+            }
+        }
+        Some(anchors)
+    }
+}
+
+/// The first `<base>` element with an `href` attribute defines the base URL
+/// of the whole document, including links in front of it
+fn find_base(text: &str) -> Option<String> {
+    Tokenizer::new(text)
+        .filter(|tag| tag.name == "base")
+        .find_map(|tag| {
+            tag.attribute("href")
+                .map(|href| href.value.trim().to_string())
+        })
+        .filter(|href| !href.is_empty())
+}
+
+/// Converts char indices of a text to line and column numbers (1-indexed)
+struct Positions {
+    /// Char index of the first char of each line
+    line_starts: Vec<usize>,
+}
+
+impl Positions {
+    fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        for (idx, c) in text.chars().enumerate() {
+            if c == '\n' {
+                line_starts.push(idx + 1);
+            }
+        }
+        Self { line_starts }
+    }
+
+    fn line_column(&self, idx: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= idx);
+        (line, idx - self.line_starts[line - 1] + 1)
+    }
+}
+
+/// Start tag of an element
+#[derive(Debug)]
+struct StartTag {
+    name: String,
+    attributes: Vec<Attribute>,
+}
+
+#[derive(Debug)]
+struct Attribute {
+    name: String,
+    /// Value with decoded character references
+    value: String,
+    /// Char index in the document of each char of the value
+    positions: Vec<usize>,
+}
+
+impl Attribute {
+    /// Value without surrounding whitespace and the char index of its start
+    fn trimmed(&self) -> Option<(usize, &str)> {
+        let start = self.value.len() - self.value.trim_start().len();
+        let trimmed = self.value.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        Some((self.positions[self.value[..start].chars().count()], trimmed))
+    }
+}
+
+impl StartTag {
+    fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    /// URLs of the tag and the char index where they start
+    fn links(&self) -> Vec<(usize, String)> {
+        let mut links = vec![];
+        if self.name == "link" {
+            if let Some(rel) = self.attribute("rel") {
+                let rel = rel.value.to_ascii_lowercase();
+                // Hints like `<link rel="preconnect" href="https://cdn.com">` name only an origin
+                if rel
+                    .split_whitespace()
+                    .all(|r| r == "preconnect" || r == "dns-prefetch")
+                {
+                    return links;
+                }
+            }
+        }
+        for (_, name) in URL_ATTRIBUTES.iter().filter(|(tag, _)| *tag == self.name) {
+            if let Some((idx, url)) = self.attribute(name).and_then(Attribute::trimmed) {
+                links.push((idx, url.to_string()));
+            }
+        }
+        if SRCSET_ELEMENTS.contains(&self.name.as_str()) {
+            if let Some(srcset) = self.attribute("srcset") {
+                links.extend(srcset_urls(srcset));
+            }
+        }
+        if self.name == "meta" {
+            let is_refresh = self
+                .attribute("http-equiv")
+                .is_some_and(|a| a.value.trim().eq_ignore_ascii_case("refresh"));
+            if let Some(content) = self.attribute("content").filter(|_| is_refresh) {
+                links.extend(refresh_url(content));
+            }
+        }
+        // Embedded data like `data:image/png;base64,...` is nothing to check
+        links.retain(|(_, url)| {
+            !url.get(..5)
+                .is_some_and(|s| s.eq_ignore_ascii_case("data:"))
+        });
+        links
+    }
+}
+
+/// URLs of the image candidate list of a `srcset` attribute, like
+/// `small.png 480w, large.png 1080w`
+fn srcset_urls(srcset: &Attribute) -> Vec<(usize, String)> {
+    let chars: Vec<char> = srcset.value.chars().collect();
+    let mut urls = vec![];
+    let mut idx = 0;
+    while idx < chars.len() {
+        while idx < chars.len() && (chars[idx].is_ascii_whitespace() || chars[idx] == ',') {
+            idx += 1;
+        }
+        let start = idx;
+        while idx < chars.len() && !chars[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        let mut url: String = chars[start..idx].iter().collect();
+        if url.ends_with(',') {
+            url = url.trim_end_matches(',').to_string();
+        } else {
+            // Skip the descriptors. Commas within parentheses do not end them.
+            let mut in_parens = false;
+            while idx < chars.len() && (in_parens || chars[idx] != ',') {
+                match chars[idx] {
+                    '(' => in_parens = true,
+                    ')' => in_parens = false,
+                    _ => {}
+                }
+                idx += 1;
+            }
+        }
+        if !url.is_empty() {
+            urls.push((srcset.positions[start], url));
+        }
+    }
+    urls
+}
+
+/// URL of a refresh like `<meta http-equiv="refresh" content="5; url=page.html">`
+fn refresh_url(content: &Attribute) -> Option<(usize, String)> {
+    let value = &content.value;
+    let mut idx =
+        value.find(|c: char| !c.is_ascii_digit() && c != '.' && !c.is_ascii_whitespace())?;
+    if value[idx..].starts_with([';', ',']) {
+        idx += 1;
+    }
+    idx += value[idx..].len() - value[idx..].trim_start().len();
+    if value[idx..]
+        .get(..3)
+        .is_some_and(|s| s.eq_ignore_ascii_case("url"))
+    {
+        let after_url = value[idx + 3..].trim_start();
+        if let Some(after_equals) = after_url.strip_prefix('=') {
+            idx = value.len() - after_equals.trim_start().len();
+        }
+    }
+    let mut url = value[idx..].trim_end();
+    if let Some(quote) = url.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        idx += 1;
+        url = &url[1..];
+        url = &url[..url.find(quote).unwrap_or(url.len())];
+    }
+    if url.is_empty() {
+        return None;
+    }
+    Some((
+        content.positions[value[..idx].chars().count()],
+        url.to_string(),
+    ))
+}
+
+/// Tokenizer which returns the start tags of a html document. Comments,
+/// doctypes, end tags and the content of raw text elements are skipped.
+struct Tokenizer {
+    chars: Vec<char>,
+    idx: usize,
+}
+
+impl Tokenizer {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            idx: 0,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.idx + offset).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek(i).is_some_and(|p| p.eq_ignore_ascii_case(&c)))
+    }
+
+    /// Skip to the char after the next occurrence of `end`
+    fn skip_past(&mut self, end: &str) {
+        while self.idx < self.chars.len() && !self.starts_with(end) {
+            self.idx += 1;
+        }
+        self.idx = (self.idx + end.chars().count()).min(self.chars.len());
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek(0).is_some_and(is_html_whitespace) {
+            self.idx += 1;
+        }
+    }
+
+    /// Skip a comment, doctype or CDATA section starting with `<!`
+    fn markup_declaration(&mut self) {
+        if self.starts_with("<!--") {
+            self.idx += 4;
+            // Abruptly closed empty comments `<!-->` and `<!--->`
+            if self.starts_with(">") {
+                self.idx += 1;
+            } else if self.starts_with("->") {
+                self.idx += 2;
+            } else {
+                while self.idx < self.chars.len()
+                    && !self.starts_with("-->")
+                    && !self.starts_with("--!>")
+                {
+                    self.idx += 1;
+                }
+                self.skip_past(">");
+            }
+        } else {
+            self.skip_past(">");
+        }
+    }
+
+    /// Tag starting at the current tag name. `None` at the end of the document.
+    fn tag(&mut self) -> Option<StartTag> {
+        let mut name = String::new();
+        while let Some(c) = self
+            .peek(0)
+            .filter(|c| !is_html_whitespace(*c) && *c != '/' && *c != '>')
+        {
+            name.push(c.to_ascii_lowercase());
+            self.idx += 1;
+        }
+        let mut attributes: Vec<Attribute> = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek(0)? {
+                '>' => {
+                    self.idx += 1;
+                    return Some(StartTag { name, attributes });
+                }
+                '/' => {
+                    self.idx += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            let attribute = self.attribute()?;
+            // Only the first of duplicated attributes is used
+            if !attributes.iter().any(|a| a.name == attribute.name) {
+                attributes.push(attribute);
+            }
+        }
+    }
+
+    fn attribute(&mut self) -> Option<Attribute> {
+        let mut name = String::new();
+        // An equals sign at the start belongs to the attribute name
+        if self.peek(0) == Some('=') {
+            name.push('=');
+            self.idx += 1;
+        }
+        while let Some(c) = self
+            .peek(0)
+            .filter(|c| !is_html_whitespace(*c) && !matches!(c, '/' | '>' | '='))
+        {
+            name.push(c.to_ascii_lowercase());
+            self.idx += 1;
+        }
+        self.skip_whitespace();
+        let mut attribute = Attribute {
+            name,
+            value: String::new(),
+            positions: vec![],
+        };
+        if self.peek(0)? != '=' {
+            return Some(attribute);
+        }
+        self.idx += 1;
+        self.skip_whitespace();
+        let (start, end) = match self.peek(0)? {
+            '>' => return Some(attribute),
+            quote @ ('"' | '\'') => {
+                let start = self.idx + 1;
+                let len = self.chars[start..].iter().position(|c| *c == quote)?;
+                self.idx = start + len + 1;
+                (start, start + len)
+            }
+            _ => {
+                let start = self.idx;
+                while self
+                    .peek(0)
+                    .is_some_and(|c| !is_html_whitespace(c) && c != '>')
+                {
+                    self.idx += 1;
+                }
+                (start, self.idx)
+            }
+        };
+        (attribute.value, attribute.positions) =
+            decode_character_references(&self.chars, start, end);
+        Some(attribute)
+    }
+
+    /// Skip the text content of a raw text element up to its end tag
+    fn raw_text(&mut self, name: &str) {
+        let end_tag = format!("</{name}");
+        while self.idx < self.chars.len() {
+            if self.starts_with(&end_tag)
+                && self
+                    .peek(end_tag.len())
+                    .is_none_or(|c| is_html_whitespace(c) || c == '/' || c == '>')
+            {
+                return;
+            }
+            self.idx += 1;
+        }
+    }
+}
+
+impl Iterator for Tokenizer {
+    type Item = StartTag;
+
+    fn next(&mut self) -> Option<StartTag> {
+        while self.idx < self.chars.len() {
+            if self.chars[self.idx] != '<' {
+                self.idx += 1;
+                continue;
+            }
+            match self.peek(1) {
+                Some('!') => self.markup_declaration(),
+                Some('?') => self.skip_past(">"),
+                Some('/') => match self.peek(2) {
+                    Some(c) if c.is_ascii_alphabetic() => {
+                        self.idx += 2;
+                        self.tag()?;
                     }
-                    ParserState::Link => {
-                        match line_chars.get(column) {
-                            Some(x) if !x.is_whitespace() && x != &'"' => {
-                                let start_col = column;
-                                while line_chars.get(column).is_some()
-                                    && !line_chars[column].is_whitespace()
-                                    && line_chars[column] != '"'
-                                {
-                                    column += 1;
-                                }
-                                while let Some(c) = line_chars.get(column) {
-                                    if c == &'"' {
-                                        break;
-                                    }
-                                    column += 1;
-                                }
-                                let mut link =
-                                    (line_chars[start_col..column]).iter().collect::<String>();
-                                if get_link_type(&link) == LinkType::FileSystem {
-                                    link = url_escape::decode(link.as_str()).to_string();
-                                };
-
-                                // Check if this line should be ignored
-                                let line_num = link_line + 1; // Convert to 1-indexed
-                                if !ignore_regions.is_line_ignored(line_num) {
-                                    result.push(Ok(MarkupLink {
-                                        column: link_column + 1,
-                                        line: line_num,
-                                        target: link.to_string(),
-                                        source: "".to_string(),
-                                    }));
-                                }
-                                state = ParserState::Text;
-                            }
-                            Some(_) | None => {}
-                        };
+                    Some('>') => self.idx += 3,
+                    _ => self.skip_past(">"),
+                },
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.idx += 1;
+                    let tag = self.tag()?;
+                    if tag.name == "plaintext" {
+                        self.idx = self.chars.len();
+                    } else if RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) {
+                        self.raw_text(&tag.name);
                     }
+                    return Some(tag);
                 }
-                column += 1;
+                _ => self.idx += 1,
             }
         }
-        result
+        None
+    }
+}
+
+fn is_html_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\u{c}')
+}
+
+/// Named character references which are common in URLs. The ones marked as
+/// legacy are also decoded without a trailing semicolon.
+const NAMED_CHARACTER_REFERENCES: [(&str, char, bool); 36] = [
+    ("amp", '&', true),
+    ("lt", '<', true),
+    ("gt", '>', true),
+    ("quot", '"', true),
+    ("nbsp", '\u{a0}', true),
+    ("copy", '©', true),
+    ("reg", '®', true),
+    ("apos", '\'', false),
+    ("sol", '/', false),
+    ("bsol", '\\', false),
+    ("colon", ':', false),
+    ("semi", ';', false),
+    ("num", '#', false),
+    ("quest", '?', false),
+    ("equals", '=', false),
+    ("percnt", '%', false),
+    ("plus", '+', false),
+    ("lpar", '(', false),
+    ("rpar", ')', false),
+    ("lsqb", '[', false),
+    ("rsqb", ']', false),
+    ("lcub", '{', false),
+    ("rcub", '}', false),
+    ("comma", ',', false),
+    ("period", '.', false),
+    ("excl", '!', false),
+    ("dollar", '$', false),
+    ("commat", '@', false),
+    ("lowbar", '_', false),
+    ("ast", '*', false),
+    ("verbar", '|', false),
+    ("grave", '`', false),
+    ("Hat", '^', false),
+    ("Tab", '\t', false),
+    ("NewLine", '\n', false),
+    ("hyphen", '\u{2010}', false),
+];
+
+/// Code points which numeric character references in the range 0x80 to 0x9F
+/// are replaced with (windows-1252)
+const C1_REPLACEMENTS: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decode character references like `&amp;` or `&#47;` of the attribute value
+/// in `chars[start..end]`. Returns the value and the char index of each char.
+fn decode_character_references(chars: &[char], start: usize, end: usize) -> (String, Vec<usize>) {
+    let mut value = String::new();
+    let mut positions = vec![];
+    let mut idx = start;
+    while idx < end {
+        let (c, len) = if chars[idx] == '&' {
+            character_reference(&chars[idx + 1..end]).map_or(('&', 1), |(c, len)| (c, len + 1))
+        } else {
+            (chars[idx], 1)
+        };
+        value.push(c);
+        positions.push(idx);
+        idx += len;
+    }
+    (value, positions)
+}
+
+/// Character of the reference following an `&` and the number of chars used
+fn character_reference(chars: &[char]) -> Option<(char, usize)> {
+    if chars.first() == Some(&'#') {
+        let hex = chars.get(1).is_some_and(|c| *c == 'x' || *c == 'X');
+        let digits_start = if hex { 2 } else { 1 };
+        let radix = if hex { 16 } else { 10 };
+        let digits = chars[digits_start..]
+            .iter()
+            .take_while(|c| c.is_digit(radix))
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        let number: String = chars[digits_start..digits_start + digits].iter().collect();
+        let code = u32::from_str_radix(&number, radix).unwrap_or(u32::MAX);
+        let c = match code {
+            0x80..=0x9F => C1_REPLACEMENTS[(code - 0x80) as usize],
+            _ => char::from_u32(code)
+                .filter(|c| *c != '\0')
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+        };
+        let mut len = digits_start + digits;
+        if chars.get(len) == Some(&';') {
+            len += 1;
+        }
+        return Some((c, len));
+    }
+
+    let name_len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .count();
+    let name: String = chars[..name_len].iter().collect();
+    let (_, c, legacy) = NAMED_CHARACTER_REFERENCES
+        .iter()
+        .find(|(reference, _, _)| *reference == name)?;
+    if chars.get(name_len) == Some(&';') {
+        Some((*c, name_len + 1))
+    } else if *legacy && chars.get(name_len) != Some(&'=') {
+        Some((*c, name_len))
+    } else {
+        None
     }
 }
 
@@ -146,8 +612,12 @@ mod tests {
         let expected = Ok(MarkupLink {
             target: "some file.html".to_string(),
             line: 1,
-            column: 6,
+            column: 15,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
@@ -159,27 +629,35 @@ mod tests {
         let expected = Ok(MarkupLink {
             target: "some file.html".to_string(),
             line: 1,
-            column: 6,
+            column: 15,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
-    #[test_case("<a href=\"https://www.w3schools.com\">Visit W3Schools.com!</a>", 1, 1)]
     #[test_case(
-        "<a\nhref\n=\n  \"https://www.w3schools.com\">\nVisit W3Schools.com!\n</a>",
+        "<a href=\"https://www.w3schools.com\">Visit W3Schools.com!</a>",
         1,
-        1
+        10
+    )]
+    #[test_case(
+        "<a\nhref\n=\n  \"https://www.w3schools.com\">\nVisit W3Schools.com!\n</a>",
+        4,
+        4
     )]
     #[test_case(
         "<a hreflang=\"en\" href=\"https://www.w3schools.com\">Visit W3Schools.com!</a>",
         1,
-        1
+        24
     )]
     #[test_case(
         "<!--comment--><a href=\"https://www.w3schools.com\">Visit W3Schools.com!</a>",
         1,
-        15
+        24
     )]
     fn links(input: &str, line: usize, column: usize) {
         let le = HtmlLinkExtractor();
@@ -189,10 +667,163 @@ mod tests {
             line,
             column,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
+    fn link(target: &str, line: usize, column: usize) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            target: target.to_string(),
+            line,
+            column,
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        })
+    }
+
+    #[test]
+    fn quoted_and_unquoted_attributes() {
+        let le = HtmlLinkExtractor();
+        let input = "<a href='single.html'>1</a> <A HREF=unquoted.html>2</A> <a href = \"spaced.html\" >3</a>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("single.html", 1, 10),
+                link("unquoted.html", 1, 37),
+                link("spaced.html", 1, 68),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn url_attributes() {
+        let le = HtmlLinkExtractor();
+        let input = "<img src=\"a.png\">\n<link rel=\"stylesheet\" href=\"b.css\">\n<script src=\"c.js\"></script>\n<iframe src=\"d.html\"></iframe>\n<video poster=\"e.jpg\" src=\"f.mp4\"></video>\n<form action=\"/g\"></form>\n<object data=\"h.svg\"></object>\n<area href=\"i.html\"><embed src=\"j.swf\"><track src=\"k.vtt\">";
+        let targets: Vec<String> = le
+            .find_links(input)
+            .into_iter()
+            .map(|l| l.unwrap().target)
+            .collect();
+        assert_eq!(
+            vec![
+                "a.png", "b.css", "c.js", "d.html", "f.mp4", "e.jpg", "/g", "h.svg", "i.html",
+                "j.swf", "k.vtt"
+            ],
+            targets
+        );
+    }
+
+    #[test]
+    fn character_references() {
+        let le = HtmlLinkExtractor();
+        let input =
+            "<a href=\"https://example.com/?a=1&amp;b=2&#38;c=3&#x26;d&copy=4&notanentity;\">x</a>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![link(
+                "https://example.com/?a=1&b=2&c=3&d&copy=4&notanentity;",
+                1,
+                10
+            )],
+            result
+        );
+    }
+
+    #[test]
+    fn srcset() {
+        let le = HtmlLinkExtractor();
+        let input = "<img src=\"a.png\" srcset=\"a-1x.png 1x, a-2x.png 2x,a-3x.png\">\n<picture><source srcset=\"b.webp\" type=\"image/webp\"></picture>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("a.png", 1, 11),
+                link("a-1x.png", 1, 26),
+                link("a-2x.png", 1, 39),
+                link("a-3x.png", 1, 51),
+                link("b.webp", 2, 26),
+            ],
+            result
+        );
+    }
+
+    #[test_case(
+        "<meta http-equiv=\"refresh\" content=\"0; url=https://example.com/\">",
+        44
+    )]
+    #[test_case(
+        "<meta http-equiv=\"Refresh\" content=\"5;URL='https://example.com/'\">",
+        44
+    )]
+    #[test_case("<meta http-equiv=\"refresh\" content=\"3 https://example.com/\">", 39)]
+    fn meta_refresh(input: &str, column: usize) {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link("https://example.com/", 1, column)], result);
+    }
+
+    #[test]
+    fn skipped_content() {
+        let le = HtmlLinkExtractor();
+        let input = "<script>document.write('<a href=\"in-script.html\">');</script>\n<style>a { background: url(\"x.png\"); }</style>\n<textarea><a href=\"in-textarea.html\"></textarea>\n<![CDATA[ <a href=\"cdata.html\"> ]]>\n<!--> <a href=\"after-empty-comment.html\">";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("after-empty-comment.html", 5, 16)], result);
+    }
+
+    #[test]
+    fn skipped_urls() {
+        let le = HtmlLinkExtractor();
+        let input = "<link rel=\"preconnect\" href=\"https://fonts.gstatic.com\">\n<img src=\"data:image/png;base64,iVBORw0KGgo=\">\n<a href=\"\">empty</a><a name=\"x\">no href</a>";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn base_href() {
+        let le = HtmlLinkExtractor();
+        let input = "<a href=\"before.html\">\n<base target=\"_blank\"><base href=\"https://example.com/docs/\">\n<base href=\"/ignored/\"><img src=\"a.png\">";
+        let result = le.find_links(input);
+        let base = Some("https://example.com/docs/".to_string());
+        let targets: Vec<_> = result
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                assert_eq!(link.base, base);
+                link.target
+            })
+            .collect();
+        assert_eq!(vec!["before.html", "a.png"], targets);
+    }
+
+    #[test]
+    fn no_base_href() {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links("<base href=\"\"><a href=\"page.html\">");
+        assert_eq!(vec![link("page.html", 1, 24)], result);
+    }
+
+    #[test]
+    fn unterminated_tag() {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links("<a href=\"ok.html\">ok</a> <img src=\"unterminated.png\"");
+        assert_eq!(vec![link("ok.html", 1, 10)], result);
+    }
+
+    #[test]
+    fn anchors() {
+        let le = HtmlLinkExtractor();
+        let input = "<h1 id=\"fn.parse\">parse</h1>\n<a name='old-name'></a>\n<section\n  class=\"x\" id=unquoted>\n<!-- <p id=\"commented\"> -->\n<input name=\"not-an-anchor\">";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["fn.parse", "old-name", "unquoted"], result);
+    }
+
     #[test]
     fn ignore_disable_line() {
         let le = HtmlLinkExtractor();
@@ -0,0 +1,268 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Maximum nesting of objects and arrays. Deeper documents are rejected
+/// instead of overflowing the stack.
+pub(crate) const MAX_DEPTH: usize = 128;
+
+/// Parsed JSON value. Only strings keep their content and position.
+#[derive(Clone, Debug)]
+pub enum Json {
+    Object(Vec<(String, Json)>),
+    Array(Vec<Json>),
+    String(JsonString),
+    Other,
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(&s.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> &[Json] {
+        match self {
+            Json::Array(values) => values,
+            _ => &[],
+        }
+    }
+
+    /// Multiline strings of notebooks are stored as a string or as an array of lines
+    pub fn text(&self) -> Option<JsonString> {
+        match self {
+            Json::String(s) => Some(s.clone()),
+            Json::Array(lines) => {
+                let mut text = JsonString::default();
+                for line in lines {
+                    if let Json::String(line) = line {
+                        text.value.push_str(&line.value);
+                        text.positions.extend_from_slice(&line.positions);
+                    }
+                }
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct JsonString {
+    pub value: String,
+    /// Line and column in the raw JSON text of each byte of the value
+    pub positions: Vec<(usize, usize)>,
+}
+
+impl JsonString {
+    /// Raw position of the given line and character column (1-indexed) of
+    /// the value
+    pub fn raw_position(&self, line: usize, column: usize) -> (usize, usize) {
+        let mut lines = self.value.split_inclusive('\n');
+        let line_start: usize = lines.by_ref().take(line - 1).map(str::len).sum();
+        let column_start: usize = lines
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(column - 1)
+            .map(char::len_utf8)
+            .sum();
+        self.positions
+            .get(line_start + column_start)
+            .or_else(|| self.positions.last())
+            .copied()
+            .unwrap_or((line, column))
+    }
+}
+
+/// Minimal JSON parser which keeps track of the positions of string characters
+pub struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// Number of objects and arrays the parser is in
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Json, String> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Ok(value),
+            Some(c) => Err(self.error(&format!("Unexpected character '{c}'"))),
+        }
+    }
+
+    fn error(&self, msg: &str) -> String {
+        format!("{msg} at line {}, column {}.", self.line, self.column)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("Expected '{expected}'"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some(c) if c.is_alphanumeric() || *c == '-' => {
+                while self
+                    .chars
+                    .peek()
+                    .is_some_and(|c| c.is_alphanumeric() || matches!(c, '-' | '+' | '.'))
+                {
+                    self.next();
+                }
+                Ok(Json::Other)
+            }
+            _ => Err(self.error("Expected value")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(&format!("Nesting deeper than {MAX_DEPTH} levels")));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.next();
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?.value;
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(members)),
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&']') {
+            self.next();
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<JsonString, String> {
+        if self.next() != Some('"') {
+            return Err(self.error("Expected string"));
+        }
+        let mut result = JsonString::default();
+        loop {
+            let position = (self.line, self.column);
+            let c = match self.next() {
+                Some('"') => return Ok(result),
+                Some('\\') => match self.next() {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('u') => self.unicode_escape()?,
+                    Some(c) => c,
+                    None => return Err(self.error("Unterminated string")),
+                },
+                Some(c) => c,
+                None => return Err(self.error("Unterminated string")),
+            };
+            result.value.push(c);
+            result
+                .positions
+                .extend(std::iter::repeat_n(position, c.len_utf8()));
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return Ok(char::from_u32(high).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+        // Characters outside of the basic multilingual plane are surrogate pairs
+        if self.next() != Some('\\') || self.next() != Some('u') {
+            return Ok(char::REPLACEMENT_CHARACTER);
+        }
+        let low = self.hex4()?;
+        let c = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+        Ok(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("Invalid unicode escape"))?;
+            value = value * 16 + digit;
+        }
+        Ok(value)
+    }
+}
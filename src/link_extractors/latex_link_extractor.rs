@@ -0,0 +1,428 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use std::path::{Path, PathBuf};
+
+use super::link_extractor::BrokenExtractedLink;
+
+/// Link extractor for LaTeX documents.
+///
+/// Supports `\href`, `\url`, `\includegraphics`, `\input`, `\include`,
+/// `\bibliography` and `\addbibresource`. Comments and verbatim environments
+/// are skipped. Like LaTeX, files are searched relative to the directory of
+/// the document with the directories of `\graphicspath` and the default file
+/// extensions of graphics, inputs and bibliographies.
+pub struct LatexLinkExtractor {
+    /// Directory in which referenced files are searched
+    pub dir: PathBuf,
+}
+
+/// Extensions pdfLaTeX tries for `\includegraphics` targets without extension
+const GRAPHICS_EXTENSIONS: [&str; 6] = ["pdf", "png", "jpg", "jpeg", "mps", "eps"];
+
+/// Environments whose content is not parsed
+const VERBATIM_ENVIRONMENTS: [&str; 9] = [
+    "verbatim",
+    "verbatim*",
+    "Verbatim",
+    "BVerbatim",
+    "LVerbatim",
+    "lstlisting",
+    "minted",
+    "comment",
+    "alltt",
+];
+
+/// Argument of a command in curly braces
+struct Group<'a> {
+    content: &'a str,
+    /// Byte offset of the content
+    start: usize,
+    /// Byte offset after the closing brace
+    end: usize,
+}
+
+impl LinkExtractor for LatexLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        let positions = Positions::new(text);
+        let mut links = vec![];
+        let mut push = |offset: usize, target: String| {
+            let (line, column) = positions.line_column(text, offset);
+            links.push(Ok(MarkupLink {
+                source: String::new(),
+                target,
+                line,
+                column,
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+            }));
+        };
+        let mut graphics_paths: Vec<String> = vec![];
+        let mut idx = 0;
+        while let Some(c) = text[idx..].chars().next() {
+            match c {
+                '%' => {
+                    idx = text[idx..].find('\n').map_or(text.len(), |end| idx + end);
+                    continue;
+                }
+                '\\' => (),
+                c => {
+                    idx += c.len_utf8();
+                    continue;
+                }
+            }
+            let name_start = idx + 1;
+            let name_len = text[name_start..]
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(text.len() - name_start);
+            if name_len == 0 {
+                // Escaped character like `\%` or `\\`
+                idx = name_start + text[name_start..].chars().next().map_or(0, char::len_utf8);
+                continue;
+            }
+            let name = &text[name_start..name_start + name_len];
+            idx = name_start + name_len;
+            if text[idx..].starts_with('*') {
+                idx += 1;
+            }
+            match name {
+                "verb" => idx = skip_verb(text, idx),
+                "begin" => {
+                    if let Some(env) = group(text, idx) {
+                        idx = env.end;
+                        if VERBATIM_ENVIRONMENTS.contains(&env.content) {
+                            let end = format!("\\end{{{}}}", env.content);
+                            idx = text[idx..].find(&end).map_or(text.len(), |i| idx + i);
+                        }
+                    }
+                }
+                "url" | "href" => {
+                    if let Some(url) = group(text, idx) {
+                        idx = url.end;
+                        let target = unescape(url.content.trim());
+                        let target = target.strip_prefix("run:").unwrap_or(&target);
+                        if !target.is_empty() {
+                            push(trimmed_start(&url), target.to_string());
+                        }
+                    }
+                }
+                "graphicspath" => {
+                    if let Some(paths) = group(text, idx) {
+                        idx = paths.end;
+                        graphics_paths = graphics_path_dirs(paths.content);
+                    }
+                }
+                "includegraphics" | "input" | "include" => {
+                    idx = skip_optional_args(text, idx);
+                    if let Some(file) = group(text, idx) {
+                        idx = file.end;
+                        let file_name = file.content.trim();
+                        if file_name.is_empty() {
+                            continue;
+                        }
+                        let target = match name {
+                            "includegraphics" => self.graphics_file(file_name, &graphics_paths),
+                            "include" => with_extension(file_name, "tex"),
+                            _ => self.input_file(file_name),
+                        };
+                        push(trimmed_start(&file), target);
+                    }
+                }
+                "bibliography" | "addbibresource" => {
+                    idx = skip_optional_args(text, idx);
+                    if let Some(files) = group(text, idx) {
+                        idx = files.end;
+                        let mut offset = files.start;
+                        for file in files.content.split(',') {
+                            let file_name = file.trim();
+                            if !file_name.is_empty() {
+                                let start = offset + file.find(file_name).unwrap_or_default();
+                                let target = match name {
+                                    "bibliography" => with_extension(file_name, "bib"),
+                                    _ => file_name.to_string(),
+                                };
+                                push(start, target);
+                            }
+                            offset += file.len() + 1;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        links
+    }
+}
+
+impl LatexLinkExtractor {
+    /// First existing file of the graphics paths with the default extensions.
+    /// The name as is if none exists.
+    fn graphics_file(&self, name: &str, graphics_paths: &[String]) -> String {
+        let has_extension = Path::new(name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                GRAPHICS_EXTENSIONS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(ext))
+            });
+        let mut files = vec![name.to_string()];
+        if !has_extension {
+            files.extend(
+                GRAPHICS_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!("{name}.{ext}")),
+            );
+        }
+        std::iter::once("")
+            .chain(graphics_paths.iter().map(String::as_str))
+            .flat_map(|dir| files.iter().map(move |file| format!("{dir}{file}")))
+            .find(|path| self.dir.join(path).is_file())
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// `\input` prefers the file with `.tex` extension, but also reads files
+    /// with other extensions
+    fn input_file(&self, name: &str) -> String {
+        let tex = with_extension(name, "tex");
+        if !self.dir.join(&tex).is_file() && self.dir.join(name).is_file() {
+            name.to_string()
+        } else {
+            tex
+        }
+    }
+}
+
+/// Argument in curly braces at the given offset. Leading whitespace is skipped.
+fn group(text: &str, idx: usize) -> Option<Group<'_>> {
+    let open = idx + text[idx..].len() - text[idx..].trim_start().len();
+    if !text[open..].starts_with('{') {
+        return None;
+    }
+    let start = open + 1;
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                return Some(Group {
+                    content: &text[start..start + i],
+                    start,
+                    end: start + i + 1,
+                })
+            }
+            '}' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Offset of the argument without leading whitespace
+fn trimmed_start(group: &Group) -> usize {
+    group.start + group.content.len() - group.content.trim_start().len()
+}
+
+/// Skip optional arguments like `[width=\textwidth]`
+fn skip_optional_args(text: &str, mut idx: usize) -> usize {
+    loop {
+        let open = idx + text[idx..].len() - text[idx..].trim_start().len();
+        if !text[open..].starts_with('[') {
+            return idx;
+        }
+        let mut depth = 0;
+        let close = text[open + 1..].char_indices().find(|(_, c)| match c {
+            '{' => {
+                depth += 1;
+                false
+            }
+            '}' => {
+                depth -= 1;
+                false
+            }
+            ']' => depth == 0,
+            _ => false,
+        });
+        match close {
+            Some((i, _)) => idx = open + 1 + i + 1,
+            None => return idx,
+        }
+    }
+}
+
+/// Skip the argument of `\verb|code|`, which is delimited by any character
+fn skip_verb(text: &str, idx: usize) -> usize {
+    let Some(delimiter) = text[idx..].chars().next() else {
+        return idx;
+    };
+    let start = idx + delimiter.len_utf8();
+    let line_end = text[start..]
+        .find('\n')
+        .map_or(text.len(), |end| start + end);
+    text[start..line_end]
+        .find(delimiter)
+        .map_or(line_end, |end| start + end + delimiter.len_utf8())
+}
+
+/// Directories of `\graphicspath{{figures/}{images/}}`
+fn graphics_path_dirs(paths: &str) -> Vec<String> {
+    paths
+        .split(['{', '}'])
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| {
+            if dir.ends_with('/') {
+                dir.to_string()
+            } else {
+                format!("{dir}/")
+            }
+        })
+        .collect()
+}
+
+/// Special characters escaped with a backslash like `\#` or `\%` in URLs
+fn unescape(url: &str) -> String {
+    let mut unescaped = String::with_capacity(url.len());
+    let mut chars = url.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && "#%&_$~{}".contains(next) => {
+                unescaped.push(next);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+fn with_extension(name: &str, extension: &str) -> String {
+    if Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+    {
+        name.to_string()
+    } else {
+        format!("{name}.{extension}")
+    }
+}
+
+/// Byte offsets of the line starts to find the line and column of a link
+struct Positions {
+    line_starts: Vec<usize>,
+}
+
+impl Positions {
+    fn new(text: &str) -> Positions {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Positions { line_starts }
+    }
+
+    fn line_column(&self, text: &str, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        (line, text[line_start..offset].chars().count() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn latex_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches")
+            .join("benchmark")
+            .join("latex")
+    }
+
+    fn targets(input: &str) -> Vec<(String, usize, usize)> {
+        let le = LatexLinkExtractor { dir: latex_dir() };
+        le.find_links(input)
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                (link.target, link.line, link.column)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn urls() {
+        let input = "See \\href{https://example.com/a\\#b}{Example} and \\url{https://example.org/a%20b}.\n\\href{run:./doc.pdf}{doc}";
+        assert_eq!(
+            vec![
+                ("https://example.com/a#b".to_string(), 1, 11),
+                ("https://example.org/a%20b".to_string(), 1, 55),
+                ("./doc.pdf".to_string(), 2, 7),
+            ],
+            targets(input)
+        );
+    }
+
+    #[test]
+    fn files() {
+        let input = "\\graphicspath{{figures/}{images/}}\n\\includegraphics[width=0.5\\textwidth]{plot}\n\\includegraphics{logo} \\includegraphics{missing}\n\\input{chapters/intro} \\include{chapters/intro}\n\\bibliography{refs, other}\n\\addbibresource{refs.bib}";
+        assert_eq!(
+            vec![
+                ("figures/plot.png".to_string(), 2, 39),
+                ("images/logo.pdf".to_string(), 3, 18),
+                ("missing".to_string(), 3, 41),
+                ("chapters/intro.tex".to_string(), 4, 8),
+                ("chapters/intro.tex".to_string(), 4, 33),
+                ("refs.bib".to_string(), 5, 15),
+                ("other.bib".to_string(), 5, 21),
+                ("refs.bib".to_string(), 6, 17),
+            ],
+            targets(input)
+        );
+    }
+
+    #[test]
+    fn graphics_without_graphicspath() {
+        let input = "\\includegraphics{plot} \\includegraphics{figures/plot}";
+        assert_eq!(
+            vec![
+                ("plot".to_string(), 1, 18),
+                ("figures/plot.png".to_string(), 1, 41),
+            ],
+            targets(input)
+        );
+    }
+
+    #[test_case("% \\url{https://example.com}")]
+    #[test_case("\\begin{verbatim}\n\\url{https://example.com}\n\\end{verbatim}")]
+    #[test_case("\\begin{lstlisting}[language=TeX]\n\\input{file}\n\\end{lstlisting}")]
+    #[test_case("\\verb|\\url{https://example.com}|")]
+    #[test_case("100\\% \\\\url{https://example.com}")]
+    #[test_case("\\url{}")]
+    fn no_link(input: &str) {
+        let result = targets(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn after_verbatim_and_comment() {
+        let input = "\\begin{verbatim}\n\\url{a}\n\\end{verbatim} 50\\% % \\url{b}\n\\url{https://example.com}";
+        assert_eq!(
+            vec![("https://example.com".to_string(), 4, 6)],
+            targets(input)
+        );
+    }
+
+    #[test]
+    fn unicode_columns() {
+        let input = "Über \\url{https://example.com}";
+        assert_eq!(
+            vec![("https://example.com".to_string(), 1, 11)],
+            targets(input)
+        );
+    }
+}
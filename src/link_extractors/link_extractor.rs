@@ -1,9 +1,20 @@
+use super::asciidoc_link_extractor::AsciiDocLinkExtractor;
 use super::html_link_extractor::HtmlLinkExtractor;
+use super::latex_link_extractor::LatexLinkExtractor;
 use super::markdown_link_extractor::MarkdownLinkExtractor;
+use super::mdx_link_extractor::MdxLinkExtractor;
+use super::notebook_link_extractor::NotebookLinkExtractor;
+use super::rst_link_extractor::RstLinkExtractor;
+use super::rust_doc_link_extractor::RustDocLinkExtractor;
+use super::slug::SlugStyle;
+use super::source_code_link_extractor::SourceCodeLinkExtractor;
+use super::structured_data_link_extractor::StructuredDataLinkExtractor;
 use crate::markup::{MarkupFile, MarkupType};
+use crate::Config;
 use std::env;
 use std::fmt;
 use std::fs;
+use std::path::Path;
 
 /// Link found in markup files
 #[derive(Eq, PartialEq, Clone)]
@@ -16,6 +27,14 @@ pub struct MarkupLink {
     pub line: usize,
     /// The column number were the link was found
     pub column: usize,
+    /// Position within an embedded document, like the cell of a notebook
+    pub location: Option<String>,
+    /// Base URL of the document relative targets are resolved against, like `<base href>` in html
+    pub base: Option<String>,
+    /// The link was found in a code block
+    pub from_code: bool,
+    /// The link uses the wiki syntax `[[Page]]` and its target is resolved against the scanned files
+    pub wiki_link: bool,
 }
 /// Broken link found in document
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -48,18 +67,28 @@ impl MarkupLink {
             static ref IS_VS_CODE_TERMINAL: bool =
                 env::var("TERM_PROGRAM") == Ok("vscode".to_string());
         }
+        let mut location = match &self.location {
+            Some(location) => format!(" [{location}]"),
+            None => String::new(),
+        };
+        if self.from_code {
+            location.push_str(" [code]");
+        }
         if *IS_VS_CODE_TERMINAL {
-            format! {"{}:{}:{} => {}", self.source, self.line, self.column, self.target}
+            format! {"{}:{}:{}{} => {}", self.source, self.line, self.column, location, self.target}
         } else {
-            format! {"{} ({}, {}) => {}", self.source, self.line, self.column, self.target}
+            format! {"{} ({}, {}){} => {}", self.source, self.line, self.column, location, self.target}
         }
     }
 }
 
 #[must_use]
-pub fn find_links(file: &MarkupFile) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+pub fn find_links(
+    file: &MarkupFile,
+    config: &Config,
+) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
     let path = &file.path;
-    let link_extractor = link_extractor_factory(file.markup_type);
+    let link_extractor = link_extractor_factory(file, config);
 
     info!("Scan file at path '{path}' for links.");
     match fs::read_to_string(path) {
@@ -84,13 +113,83 @@ pub fn find_links(file: &MarkupFile) -> Vec<Result<MarkupLink, BrokenExtractedLi
     }
 }
 
-fn link_extractor_factory(markup_type: MarkupType) -> Box<dyn LinkExtractor> {
-    match markup_type {
-        MarkupType::Markdown => Box::new(MarkdownLinkExtractor()),
+/// Find all anchors (link fragment targets) defined in the given file.
+///
+/// Returns `None` if the file could not be read or anchors of the markup type
+/// are not known.
+#[must_use]
+pub fn find_anchors(file: &MarkupFile, slug_style: SlugStyle) -> Option<Vec<String>> {
+    let path = &file.path;
+    let link_extractor = link_extractor_factory(file, &Config::default());
+
+    info!("Scan file at path '{path}' for anchors.");
+    match fs::read_to_string(path) {
+        Ok(text) => link_extractor.find_anchors(&text, slug_style),
+        Err(e) => {
+            warn!("File '{path}'. IO Error: \"{e}\". Check your file encoding.");
+            None
+        }
+    }
+}
+
+/// Markdown link extractor with the options of the config. It is also used
+/// for markdown embedded in other markup types.
+fn markdown_link_extractor(config: &Config) -> MarkdownLinkExtractor {
+    MarkdownLinkExtractor {
+        autolink_literals: config.optional.autolink_literals.unwrap_or_default(),
+        code_block_languages: config
+            .optional
+            .code_block_languages
+            .clone()
+            .unwrap_or_default(),
+        front_matter_keys: config
+            .optional
+            .front_matter_keys
+            .clone()
+            .unwrap_or_default(),
+        wiki_links: config.optional.wiki_links.is_some(),
+    }
+}
+
+fn link_extractor_factory(file: &MarkupFile, config: &Config) -> Box<dyn LinkExtractor> {
+    match file.markup_type {
+        MarkupType::Markdown => Box::new(markdown_link_extractor(config)),
         MarkupType::Html => Box::new(HtmlLinkExtractor()),
+        MarkupType::RestructuredText => Box::new(RstLinkExtractor()),
+        MarkupType::AsciiDoc => Box::new(AsciiDocLinkExtractor()),
+        MarkupType::Notebook => Box::new(NotebookLinkExtractor {
+            markdown: markdown_link_extractor(config),
+        }),
+        MarkupType::Mdx => Box::new(MdxLinkExtractor {
+            markdown: markdown_link_extractor(config),
+        }),
+        MarkupType::RustDoc => Box::new(RustDocLinkExtractor {
+            markdown: markdown_link_extractor(config),
+        }),
+        MarkupType::Latex => Box::new(LatexLinkExtractor {
+            dir: Path::new(&file.path)
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        }),
+        MarkupType::StructuredData => Box::new(StructuredDataLinkExtractor::new(
+            &file.path,
+            &config.directory,
+            config
+                .optional
+                .structured_data
+                .as_deref()
+                .unwrap_or_default(),
+        )),
+        MarkupType::SourceCode => Box::new(SourceCodeLinkExtractor::new(&file.path)),
     }
 }
 
 pub trait LinkExtractor {
     fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>>;
+
+    /// Anchors which can be the target of a link fragment. `None` if unknown.
+    fn find_anchors(&self, _text: &str, _slug_style: SlugStyle) -> Option<Vec<String>> {
+        None
+    }
 }
@@ -1,11 +1,45 @@
+use super::front_matter::FrontMatter;
 use super::html_link_extractor::HtmlLinkExtractor;
 use super::ignore_comments::IgnoreRegions;
 use super::link_extractor::BrokenExtractedLink;
+use super::slug::{SlugStyle, Slugger};
 use crate::link_extractors::link_extractor::LinkExtractor;
 use crate::link_extractors::link_extractor::MarkupLink;
-use pulldown_cmark::{BrokenLink, Event, Options, Parser, Tag};
+use pulldown_cmark::{
+    BrokenLink, CodeBlockKind, Event, LinkType, Options, Parser, RefDefs, Tag, TagEnd,
+};
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
 
-pub struct MarkdownLinkExtractor();
+const OPTIONS: Options = Options::ENABLE_TASKLISTS.union(Options::ENABLE_FOOTNOTES);
+
+#[derive(Default)]
+pub struct MarkdownLinkExtractor {
+    /// Find bare URLs like `https://example.com` or `www.example.com` in text
+    /// the way GitHub renders them as links (GFM autolink literals)
+    pub autolink_literals: bool,
+    /// Languages of fenced code blocks in which URLs are checked
+    pub code_block_languages: Vec<String>,
+    /// Keys of the YAML or TOML front matter whose values are checked
+    pub front_matter_keys: Vec<String>,
+    /// Find wiki links like `[[Page]]`, `[[Page|alias]]` or `![[image.png]]`
+    pub wiki_links: bool,
+}
+
+impl MarkdownLinkExtractor {
+    fn is_checked_code_block(&self, info: &str) -> bool {
+        let language = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()
+            .unwrap_or_default();
+        !language.is_empty()
+            && self
+                .code_block_languages
+                .iter()
+                .any(|checked| checked.eq_ignore_ascii_case(language))
+    }
+}
 
 impl LinkExtractor for MarkdownLinkExtractor {
     fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
@@ -13,6 +47,16 @@ impl LinkExtractor for MarkdownLinkExtractor {
         let result: RefCell<Vec<Result<MarkupLink, BrokenExtractedLink>>> =
             RefCell::new(Vec::new());
 
+        let front_matter = FrontMatter::find(text);
+        let blanked;
+        let text = match &front_matter {
+            Some(front_matter) => {
+                blanked = front_matter.blank(text);
+                blanked.as_str()
+            }
+            None => text,
+        };
+
         let html_extractor = HtmlLinkExtractor();
         let converter = LineColumnConverter::new(text);
         let ignore_regions = IgnoreRegions::from_text(text);
@@ -39,12 +83,133 @@ impl LinkExtractor for MarkdownLinkExtractor {
             None
         };
 
-        let parser =
-            Parser::new_with_broken_link_callback(text, Options::ENABLE_TASKLISTS, Some(callback));
+        let mut options = OPTIONS;
+        if self.wiki_links {
+            options.insert(Options::ENABLE_WIKILINKS);
+        }
+        let parser = Parser::new_with_broken_link_callback(text, options, Some(callback));
+        let mut footnotes = Footnotes::default();
+        let mut references = References::new(parser.reference_definitions());
+        let find_autolink_literals = |run: Range<usize>| {
+            for (idx, target) in autolink_literals(text, run) {
+                let (line, column) = converter.line_column_from_idx(idx);
+                if ignore_regions.is_line_ignored(line) {
+                    continue;
+                }
+                result.borrow_mut().push(Ok(MarkupLink {
+                    line,
+                    column,
+                    source: String::new(),
+                    location: None,
+                    base: None,
+                    from_code: false,
+                    wiki_link: false,
+                    target,
+                }));
+            }
+        };
+        // Contiguous text outside of links and code which may contain bare URLs
+        let mut text_run: Option<Range<usize>> = None;
+        let mut link_or_code_depth = 0;
+        // Inside a code block of a language listed in `code_block_languages`
+        let mut in_checked_code = false;
 
         for (evt, range) in parser.into_offset_iter() {
+            if let Event::Text(_) = evt {
+                footnotes.text(text, range.clone());
+            } else {
+                footnotes.open = None;
+            }
+            match &evt {
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    link_or_code_depth += 1;
+                    in_checked_code = match kind {
+                        CodeBlockKind::Fenced(info) => self.is_checked_code_block(info),
+                        CodeBlockKind::Indented => false,
+                    };
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    link_or_code_depth -= 1;
+                    in_checked_code = false;
+                }
+                Event::Start(Tag::Link { .. } | Tag::Image { .. }) => {
+                    link_or_code_depth += 1;
+                }
+                Event::End(TagEnd::Link | TagEnd::Image) => {
+                    link_or_code_depth -= 1;
+                }
+                _ => (),
+            }
+            if self.autolink_literals {
+                text_run = match (text_run.take(), &evt) {
+                    (Some(run), Event::Text(_)) if run.end == range.start => {
+                        Some(run.start..range.end)
+                    }
+                    (run, evt) => {
+                        if let Some(run) = run {
+                            find_autolink_literals(run);
+                        }
+                        match evt {
+                            Event::Text(_) if link_or_code_depth == 0 => Some(range.clone()),
+                            _ => None,
+                        }
+                    }
+                };
+            }
             match evt {
-                Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. }) => {
+                Event::Text(_) if in_checked_code => {
+                    for (idx, target) in code_urls(&text[range.clone()]) {
+                        let (line, column) = converter.line_column_from_idx(range.start + idx);
+                        if ignore_regions.is_line_ignored(line) {
+                            continue;
+                        }
+                        result.borrow_mut().push(Ok(MarkupLink {
+                            line,
+                            column,
+                            source: String::new(),
+                            location: None,
+                            base: None,
+                            from_code: true,
+                            wiki_link: false,
+                            target,
+                        }));
+                    }
+                }
+                Event::FootnoteReference(label) => {
+                    footnotes.references.insert(normalize_label(&label));
+                }
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    footnotes.definitions.push((label.to_string(), range.start));
+                }
+                Event::Start(
+                    Tag::Paragraph
+                    | Tag::Heading { .. }
+                    | Tag::CodeBlock(_)
+                    | Tag::HtmlBlock
+                    | Tag::Table(_),
+                ) => {
+                    references.blocks.push(range);
+                }
+                Event::Start(
+                    Tag::Link {
+                        link_type,
+                        dest_url,
+                        id,
+                        ..
+                    }
+                    | Tag::Image {
+                        link_type,
+                        dest_url,
+                        id,
+                        ..
+                    },
+                ) => {
+                    if matches!(
+                        link_type,
+                        LinkType::Reference | LinkType::Collapsed | LinkType::Shortcut
+                    ) {
+                        references.used.insert(normalize_label(&id));
+                    }
                     let line_col = converter.line_column_from_idx(range.start);
 
                     // Skip if line is ignored
@@ -56,6 +221,10 @@ impl LinkExtractor for MarkdownLinkExtractor {
                         line: line_col.0,
                         column: line_col.1,
                         source: String::new(),
+                        location: None,
+                        base: None,
+                        from_code: false,
+                        wiki_link: matches!(link_type, LinkType::WikiLink { .. }),
                         target: dest_url.to_string(),
                     }));
                 }
@@ -76,6 +245,10 @@ impl LinkExtractor for MarkdownLinkExtractor {
                                 column,
                                 line,
                                 source: md_link.source.clone(),
+                                location: None,
+                                base: None,
+                                from_code: false,
+                                wiki_link: false,
                                 target: md_link.target.clone(),
                             })
                         })
@@ -93,38 +266,408 @@ impl LinkExtractor for MarkdownLinkExtractor {
                 _ => (),
             };
         }
-        result.into_inner()
+        if let Some(run) = text_run {
+            find_autolink_literals(run);
+        }
+
+        let mut result = result.into_inner();
+        // Used definitions are checked as well, so that a broken URL is
+        // reported at the definition and at every usage
+        for (_, dest, idx) in references.used_definitions() {
+            let (line, column) = converter.line_column_from_idx(*idx);
+            if ignore_regions.is_line_ignored(line) {
+                continue;
+            }
+            result.push(Ok(MarkupLink {
+                line,
+                column,
+                source: String::new(),
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+                target: dest.to_string(),
+            }));
+        }
+
+        let front_matter_values = front_matter.map(|f| f.values()).unwrap_or_default();
+        for value in front_matter_values {
+            let checked = value
+                .path
+                .first()
+                .is_some_and(|key| self.front_matter_keys.contains(key));
+            if !checked || !is_front_matter_link(&value.value) {
+                continue;
+            }
+            let (line, column) = converter.line_column_from_idx(value.offset);
+            if ignore_regions.is_line_ignored(line) {
+                continue;
+            }
+            result.push(Ok(MarkupLink {
+                line,
+                column,
+                source: String::new(),
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+                target: value.value,
+            }));
+        }
+
+        let mut problems = footnotes.problems();
+        problems.append(&mut references.problems(text, &converter));
+        problems.sort_by_key(|(_, idx, _)| *idx);
+        for (reference, idx, error) in problems {
+            let (line, column) = converter.line_column_from_idx(idx);
+            if ignore_regions.is_line_ignored(line) {
+                continue;
+            }
+            result.push(Err(BrokenExtractedLink {
+                source: String::new(),
+                reference,
+                line,
+                column,
+                error,
+            }));
+        }
+        result
+    }
+
+    fn find_anchors(&self, text: &str, slug_style: SlugStyle) -> Option<Vec<String>> {
+        let blanked;
+        let text = match FrontMatter::find(text) {
+            Some(front_matter) => {
+                blanked = front_matter.blank(text);
+                blanked.as_str()
+            }
+            None => text,
+        };
+        let html_extractor = HtmlLinkExtractor();
+        let mut anchors = vec![];
+        let mut slugger = Slugger::new(slug_style);
+        let mut heading: Option<String> = None;
+        let mut custom_id: Option<String> = None;
+
+        let options = Options::ENABLE_TASKLISTS | Options::ENABLE_HEADING_ATTRIBUTES;
+        for evt in Parser::new_ext(text, options) {
+            match evt {
+                Event::Start(Tag::Heading { id, .. }) => {
+                    heading = Some(String::new());
+                    custom_id = id.map(|id| id.to_string());
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some(heading_text) = heading.take() {
+                        let anchor = match custom_id.take() {
+                            Some(id) => slugger.custom(&id),
+                            None => slugger.slug(&heading_text),
+                        };
+                        anchors.push(anchor);
+                    }
+                }
+                Event::Text(t) | Event::Code(t) => {
+                    if let Some(heading_text) = heading.as_mut() {
+                        heading_text.push_str(&t);
+                    }
+                }
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    if let Some(mut html_anchors) = html_extractor.find_anchors(&html, slug_style) {
+                        anchors.append(&mut html_anchors);
+                    }
+                }
+                _ => (),
+            }
+        }
+        Some(anchors)
+    }
+}
+
+/// Footnote references and definitions of a markdown document
+#[derive(Default)]
+struct Footnotes {
+    /// Normalized labels of all resolved references
+    references: HashSet<String>,
+    /// Label and offset of all definitions
+    definitions: Vec<(String, usize)>,
+    /// Label and offset of references without definition
+    missing: Vec<(String, usize)>,
+    /// Offset of an unmatched `[` and the label which followed it
+    open: Option<(usize, Option<String>)>,
+}
+
+impl Footnotes {
+    /// References without definition are no footnote references for the
+    /// parser. They end up as the text events `[`, `^label` and `]`.
+    fn text(&mut self, text: &str, range: Range<usize>) {
+        let raw = &text[range.clone()];
+        self.open = match (self.open.take(), raw) {
+            (_, "[") => Some((range.start, None)),
+            (Some((start, None)), raw)
+                if raw.len() > 1
+                    && raw.starts_with('^')
+                    && !raw.contains(|c: char| c.is_whitespace() || c == ']') =>
+            {
+                Some((start, Some(raw[1..].to_string())))
+            }
+            (Some((start, Some(label))), "]") => {
+                self.missing.push((label, start));
+                None
+            }
+            _ => None,
+        };
+    }
+
+    /// Reference, offset and error of missing and unused footnotes
+    fn problems(self) -> Vec<(String, usize, String)> {
+        let references = self.references;
+        let missing = self.missing.into_iter().map(|(label, idx)| {
+            let error = "Footnote definition not found".to_string();
+            (format!("^{label}"), idx, error)
+        });
+        let unused = self
+            .definitions
+            .into_iter()
+            .filter(|(label, _)| !references.contains(&normalize_label(label)))
+            .map(|(label, idx)| {
+                let error = "Footnote is never referenced".to_string();
+                (format!("^{label}"), idx, error)
+            });
+        missing.chain(unused).collect()
     }
 }
 
-struct LineColumnConverter {
-    line_lengths: Vec<usize>,
+/// Reference definitions like `[label]: https://example.com` of a markdown
+/// document and the labels used by links
+struct References {
+    /// Label, destination and offset of the definitions known to the parser
+    definitions: Vec<(String, String, usize)>,
+    /// Normalized labels used by reference links
+    used: HashSet<String>,
+    /// Ranges of blocks which cannot contain definitions
+    blocks: Vec<Range<usize>>,
 }
 
-impl LineColumnConverter {
-    fn new(text: &str) -> Self {
-        let mut line_lengths: Vec<usize> = Vec::new();
-        let mut current_line_len = 0;
-        for c in text.chars() {
-            current_line_len += c.len_utf8();
-            if c == '\n' {
-                line_lengths.push(current_line_len);
-                current_line_len = 0;
+impl References {
+    fn new(reference_definitions: &RefDefs) -> Self {
+        let mut definitions: Vec<_> = reference_definitions
+            .iter()
+            .map(|(label, def)| (label.to_string(), def.dest.to_string(), def.span.start))
+            .collect();
+        definitions.sort_by_key(|(_, _, idx)| *idx);
+        References {
+            definitions,
+            used: HashSet::new(),
+            blocks: vec![],
+        }
+    }
+
+    fn used_definitions(&self) -> impl Iterator<Item = &(String, String, usize)> {
+        self.definitions
+            .iter()
+            .filter(move |(label, _, _)| self.used.contains(&normalize_label(label)))
+    }
+
+    /// Reference, offset and error of unused and duplicate definitions
+    fn problems(
+        &self,
+        text: &str,
+        converter: &LineColumnConverter<'_>,
+    ) -> Vec<(String, usize, String)> {
+        let mut problems: Vec<_> = self
+            .definitions
+            .iter()
+            .filter(|(label, _, _)| !self.used.contains(&normalize_label(label)))
+            .map(|(label, _, idx)| {
+                let error = "Reference definition is never used".to_string();
+                (label.to_string(), *idx, error)
+            })
+            .collect();
+
+        // The parser only keeps the first definition of a label
+        for (label, idx) in self.hidden_definitions(text) {
+            let first = self
+                .definitions
+                .iter()
+                .find(|(first, _, _)| normalize_label(first) == normalize_label(&label));
+            if let Some((_, _, first_idx)) = first {
+                let (line, column) = converter.line_column_from_idx(*first_idx);
+                let error = format!(
+                    "Duplicate reference definition. The label is already defined at line {line}, column {column}"
+                );
+                problems.push((label, idx, error));
             }
         }
-        Self { line_lengths }
+        problems
     }
 
-    fn line_column_from_idx(&self, idx: usize) -> (usize, usize) {
-        let mut line = 1;
-        let mut column = idx + 1;
-        for line_length in &self.line_lengths {
-            if *line_length >= column {
-                return (line, column);
+    /// Label and offset of definitions which were dropped by the parser
+    fn hidden_definitions(&self, text: &str) -> Vec<(String, usize)> {
+        lazy_static! {
+            static ref DEFINITION_START: Regex = Regex::new(r"(?m)^[ \t>]*\[").unwrap();
+        }
+        let mut hidden = vec![];
+        for candidate in DEFINITION_START.find_iter(text) {
+            let idx = candidate.end() - 1;
+            if self
+                .definitions
+                .iter()
+                .any(|(_, _, def_idx)| *def_idx == idx)
+                || self.blocks.iter().any(|block| block.contains(&idx))
+            {
+                continue;
+            }
+            // A definition ends at the latest with the next blank line
+            let rest = &text[idx..];
+            let end = rest.find("\n\n").unwrap_or(rest.len());
+            let parser = Parser::new_ext(&rest[..end], OPTIONS);
+            let label = parser
+                .reference_definitions()
+                .iter()
+                .find(|(_, def)| def.span.start == 0)
+                .map(|(label, _)| label.to_string());
+            if let Some(label) = label {
+                hidden.push((label, idx));
             }
-            column -= line_length;
-            line += 1;
         }
+        hidden
+    }
+}
+
+/// Byte offset and target of bare URLs which GitHub renders as links
+/// (GFM autolink literals)
+fn autolink_literals(text: &str, run: Range<usize>) -> Vec<(usize, String)> {
+    lazy_static! {
+        static ref URL_START: Regex = Regex::new(r"(?i)https?://|www\.").unwrap();
+    }
+    let mut links = vec![];
+    let mut searched = run.start;
+    for start_match in URL_START.find_iter(&text[run.clone()]) {
+        let start = run.start + start_match.start();
+        // Autolinks start at the beginning of a line, after whitespace or
+        // after one of the delimiters `*`, `_`, `~` and `(`
+        let is_delimited = text[..start]
+            .chars()
+            .last()
+            .is_none_or(|c| c.is_whitespace() || "*_~(".contains(c));
+        if start < searched || !is_delimited {
+            continue;
+        }
+
+        let is_www = start_match.as_str().eq_ignore_ascii_case("www.");
+        let domain_start = if is_www {
+            start
+        } else {
+            run.start + start_match.end()
+        };
+        let domain_len = text[domain_start..run.end]
+            .find(|c: char| !(c.is_alphanumeric() || "_-.".contains(c)))
+            .unwrap_or(run.end - domain_start);
+        let domain = &text[domain_start..domain_start + domain_len];
+        if !is_valid_domain(domain.trim_end_matches('.'), !is_www) {
+            continue;
+        }
+
+        let len = text[start..run.end]
+            .find(|c: char| c.is_whitespace() || c == '<')
+            .unwrap_or(run.end - start);
+        let url = trim_autolink(&text[start..start + len]);
+        if url.len() <= start_match.len() {
+            continue;
+        }
+        searched = start + url.len();
+        let target = if is_www {
+            format!("http://{url}")
+        } else {
+            url.to_string()
+        };
+        links.push((start, target));
+    }
+    links
+}
+
+/// Byte offset and target of web links in code. URLs with template
+/// placeholders like `${VERSION}` or `{{ site.url }}` cannot be checked.
+pub(super) fn code_urls(code: &str) -> Vec<(usize, String)> {
+    lazy_static! {
+        static ref URL: Regex = Regex::new(r#"https?://[^\s"'<>`\\]+"#).unwrap();
+    }
+    URL.find_iter(code)
+        .map(|url| (url.start(), trim_autolink(url.as_str())))
+        .filter(|(_, url)| !url.contains("${") && !url.contains("{{"))
+        .map(|(idx, url)| (idx, url.to_string()))
+        .collect()
+}
+
+/// Domain segments are separated by periods and the last two segments
+/// contain no underscores. A period is only optional after a scheme.
+fn is_valid_domain(domain: &str, allow_short: bool) -> bool {
+    let segments: Vec<&str> = domain.split('.').collect();
+    (allow_short || segments.len() > 1)
+        && segments.iter().all(|segment| !segment.is_empty())
+        && segments
+            .iter()
+            .rev()
+            .take(2)
+            .all(|segment| !segment.contains('_'))
+}
+
+/// Remove trailing punctuation, unbalanced closing parentheses and entity
+/// references which are not part of an autolink
+pub(super) fn trim_autolink(url: &str) -> &str {
+    lazy_static! {
+        static ref ENTITY_END: Regex = Regex::new(r"&[[:alnum:]]+;$").unwrap();
+    }
+    let mut url = url;
+    loop {
+        if let Some(trimmed) = url.strip_suffix(|c: char| "?!.,:*_~'\"".contains(c)) {
+            url = trimmed;
+        } else if url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+            url = &url[..url.len() - 1];
+        } else if let Some(entity) = ENTITY_END.find(url) {
+            url = &url[..entity.start()];
+        } else {
+            return url;
+        }
+    }
+}
+
+/// Front matter values like titles or dates are skipped. Links contain no
+/// whitespace and a path separator, a file extension or a scheme.
+fn is_front_matter_link(value: &str) -> bool {
+    !value.is_empty() && !value.contains(char::is_whitespace) && value.contains(['/', '.', ':'])
+}
+
+/// Labels are matched case-insensitively with collapsed whitespace
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Converts byte offsets to lines and character columns
+struct LineColumnConverter<'a> {
+    text: &'a str,
+    /// Byte offsets of the line starts
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineColumnConverter<'a> {
+    fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    fn line_column_from_idx(&self, idx: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= idx);
+        let line_start = self.line_starts[line - 1];
+        let column = match self.text.get(line_start..idx) {
+            Some(before) => before.chars().count() + 1,
+            None => idx - line_start + 1,
+        };
         (line, column)
     }
 }
@@ -136,7 +679,7 @@ mod tests {
 
     #[test]
     fn inline_no_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "]This is not a () link](! has no title attribute.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -144,7 +687,7 @@ mod tests {
 
     #[test]
     fn commented_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "]This is not a () <!--[link](link)-->.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -152,7 +695,7 @@ mod tests {
 
     #[test]
     fn nested_links() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input =
             "\n\r\t\n[![](http://meritbadge.herokuapp.com/mlc)](https://crates.io/crates/mlc)";
         let result = le.find_links(input);
@@ -161,19 +704,27 @@ mod tests {
             line: 3,
             column: 2,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         let link = Ok(MarkupLink {
             target: "https://crates.io/crates/mlc".to_string(),
             line: 3,
             column: 1,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![link, img], result);
     }
 
     #[test]
     fn link_escaped() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "This is not a \\[link\\](random_link).";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -181,15 +732,30 @@ mod tests {
 
     #[test]
     fn link_in_headline() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "  # This is a [link](http://example.net/).";
         let result = le.find_links(input);
         assert_eq!(result[0].as_ref().unwrap().column, 15);
     }
 
+    #[test]
+    fn multi_byte_prefix() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "Größe [a](https://a.com) <a href=\"https://b.com\">b</a>\n\n<p>\n  Ä <a href=\"https://c.com\">c</a>\n</p>";
+        let columns: Vec<(usize, usize)> = le
+            .find_links(input)
+            .iter()
+            .map(|link| {
+                let link = link.as_ref().unwrap();
+                (link.line, link.column)
+            })
+            .collect();
+        assert_eq!(vec![(1, 7), (1, 35), (4, 14)], columns);
+    }
+
     #[test]
     fn no_link_colon() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "This is not a [link:bla.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -197,7 +763,7 @@ mod tests {
 
     #[test]
     fn broken_reference_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "This is not a [link]:bla.";
         let result = le.find_links(input);
 
@@ -213,7 +779,7 @@ mod tests {
 
     #[test]
     fn inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " `[code](http://example.net/)`, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -221,7 +787,7 @@ mod tests {
 
     #[test]
     fn link_near_inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " `bug` [code](http://example.net/), link!.";
         let result = le.find_links(input);
         let expected = Ok(MarkupLink {
@@ -229,13 +795,17 @@ mod tests {
             line: 1,
             column: 8,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn link_very_near_inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "`bug`[code](http://example.net/)";
         let result = le.find_links(input);
         let expected = Ok(MarkupLink {
@@ -243,13 +813,17 @@ mod tests {
             line: 1,
             column: 6,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " ``` js\n[code](http://example.net/)```, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -257,7 +831,7 @@ mod tests {
 
     #[test]
     fn html_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<script>\n[code](http://example.net/)</script>, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -265,7 +839,7 @@ mod tests {
 
     #[test]
     fn escaped_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "   klsdjf \\`[escape](http://example.net/)\\`, no link!.";
         let result = le.find_links(input);
         let expected = Ok(MarkupLink {
@@ -273,13 +847,17 @@ mod tests {
             line: 1,
             column: 13,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn link_in_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "```\n[only code](http://example.net/)\n```.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -287,7 +865,7 @@ mod tests {
 
     #[test]
     fn image_reference() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("\n\nBla ![This is an image link]({link_str})");
         let result = le.find_links(&input);
@@ -296,13 +874,17 @@ mod tests {
             line: 3,
             column: 5,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn link_no_title() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("[This link]({link_str}) has no title attribute.");
         let result = le.find_links(&input);
@@ -311,13 +893,17 @@ mod tests {
             line: 1,
             column: 1,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn link_with_title() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("\n123[This is a link]({link_str} \"with title\") oh yea.");
         let result = le.find_links(&input);
@@ -326,6 +912,10 @@ mod tests {
             line: 2,
             column: 4,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
@@ -336,13 +926,17 @@ mod tests {
     //#[test_case("http://example.net/", 1)]
     #[test_case("This is a short link <http://example.net/>", 22)]
     fn inline_link(input: &str, column: usize) {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links(input);
         let expected = Ok(MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
@@ -356,85 +950,168 @@ mod tests {
         test_name = "html_link_no_target"
     )]
     fn html_link(input: &str) {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links(input);
         let expected = Ok(MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
-            column: 1,
+            column: 10,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn html_link_ident() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links("123<a href=\"http://example.net/\"> link text</a>");
         let expected = Ok(MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
-            column: 4,
+            column: 13,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn html_link_new_line() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links("\n123<a href=\"http://example.net/\"> link text</a>");
         let expected = Ok(MarkupLink {
             target: "http://example.net/".to_string(),
             line: 2,
-            column: 4,
+            column: 13,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn raw_html_issue_31() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links("Some text <a href=\"some_url\">link text</a> more text.");
         let expected = Ok(MarkupLink {
             target: "some_url".to_string(),
             line: 1,
-            column: 11,
+            column: 20,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
         assert_eq!(vec![expected], result);
     }
 
     #[test]
     fn referenced_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!(
             "This is [an example][arbitrary case-insensitive reference text] reference-style link.\n\n[Arbitrary CASE-insensitive reference text]: {link_str}"
         );
         let result = le.find_links(&input);
-        let expected = Ok(MarkupLink {
+        let usage = Ok(MarkupLink {
             target: link_str.to_string(),
             line: 1,
             column: 9,
             source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
         });
-        assert_eq!(vec![expected], result);
+        let definition = Ok(MarkupLink {
+            target: link_str.to_string(),
+            line: 3,
+            column: 1,
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        });
+        assert_eq!(vec![usage, definition], result);
     }
 
     #[test]
     fn referenced_link_tag_only() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("Foo Bar\n\n[Arbitrary CASE-insensitive reference text]: {link_str}");
         let result = le.find_links(&input);
-        assert_eq!(0, result.len());
+        let unused = Err(BrokenExtractedLink {
+            source: "".to_string(),
+            reference: "Arbitrary CASE-insensitive reference text".to_string(),
+            line: 3,
+            column: 1,
+            error: "Reference definition is never used".to_string(),
+        });
+        assert_eq!(vec![unused], result);
+    }
+
+    #[test]
+    fn duplicate_reference_definitions() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "[a][Foo  Bar] and [b].\n\n[foo bar]: ./first.md\n  [FOO BAR]: ./second.md\n\n> [b]: ./b.md\n> [B]: ./b2.md\n\n```\n[foo bar]: ./code.md\n```\nText [foo bar]: ./text.md";
+        let result = le.find_links(input);
+        let problems: Vec<_> = result
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .map(|e| (e.reference.as_str(), e.line, e.column, e.error.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "FOO BAR",
+                    4,
+                    3,
+                    "Duplicate reference definition. The label is already defined at line 3, column 1"
+                ),
+                (
+                    "B",
+                    7,
+                    3,
+                    "Duplicate reference definition. The label is already defined at line 6, column 3"
+                ),
+            ],
+            problems
+        );
+        let targets: Vec<_> = result
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .map(|l| l.target.as_str())
+            .collect();
+        assert_eq!(
+            vec!["./first.md", "./b.md", "./first.md", "./first.md", "./b.md"],
+            targets
+        );
+    }
+
+    #[test]
+    fn ignore_reference_definitions() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "Text\n\n<!-- mlc-disable-next-line -->\n[unused]: ./unused.md";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
     }
 
     #[test]
     fn referenced_link_no_tag_only() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "[link][reference]";
         let result = le.find_links(input);
         assert_eq!(1, result.len());
@@ -442,7 +1119,7 @@ mod tests {
 
     #[test]
     fn ignore_disable_line() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<!-- mlc-disable-line --> [link](http://example.net/)";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -450,7 +1127,7 @@ mod tests {
 
     #[test]
     fn ignore_disable_next_line() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<!-- mlc-disable-next-line -->\n[link](http://example.net/)";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -458,7 +1135,7 @@ mod tests {
 
     #[test]
     fn ignore_disable_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<!-- mlc-disable -->\n[link1](http://example.net/)\n<!-- mlc-enable -->\n[link2](http://example.com/)";
         let result = le.find_links(input);
         assert_eq!(1, result.len());
@@ -468,7 +1145,7 @@ mod tests {
 
     #[test]
     fn ignore_multiple_blocks() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "[link1](http://a.com/)\n<!-- mlc-disable -->\n[link2](http://b.com/)\n<!-- mlc-enable -->\n[link3](http://c.com/)\n<!-- mlc-disable -->\n[link4](http://d.com/)\n<!-- mlc-enable -->\n[link5](http://e.com/)";
         let result = le.find_links(input);
         assert_eq!(3, result.len());
@@ -479,7 +1156,7 @@ mod tests {
 
     #[test]
     fn ignore_html_link_in_markdown() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<!-- mlc-disable-next-line -->\n<a href=\"http://example.net/\">link</a>";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -487,7 +1164,7 @@ mod tests {
 
     #[test]
     fn ignore_mixed_types() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "[link1](http://a.com/)\n<!-- mlc-disable-line --> [link2](http://b.com/)\n[link3](http://c.com/)";
         let result = le.find_links(input);
         assert_eq!(2, result.len());
@@ -495,9 +1172,56 @@ mod tests {
         assert_eq!(result[1].as_ref().unwrap().target, "http://c.com/");
     }
 
+    #[test]
+    fn anchors_from_headings() {
+        let le = MarkdownLinkExtractor::default();
+        let input =
+            "# Chapter 1\n\nSome text\n\n## The `run` *function*\n\nSetext\n---\n\n# Chapter 1";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(
+            vec!["chapter-1", "the-run-function", "setext", "chapter-1-1"],
+            result
+        );
+    }
+
+    #[test]
+    fn anchors_from_html() {
+        let le = MarkdownLinkExtractor::default();
+        let input =
+            "# Heading\n\n<a name=\"legacy\"></a>\n\nText with <span id=\"inline\">span</span>.";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["heading", "legacy", "inline"], result);
+    }
+
+    #[test]
+    fn anchors_with_custom_id() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "# Installation {#install}\n\n## Usage\n\n## Usage {#usage}";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["install", "usage", "usage"], result);
+    }
+
+    #[test]
+    fn anchors_with_slug_style() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "# Ärger & Co\n\n# Ärger & Co";
+        let result = le.find_anchors(input, SlugStyle::GitLab).unwrap();
+        assert_eq!(vec!["ärger-co", "ärger-co-1"], result);
+        let result = le.find_anchors(input, SlugStyle::MdBook).unwrap();
+        assert_eq!(vec!["Ärger--co", "Ärger--co-1"], result);
+    }
+
+    #[test]
+    fn anchors_ignore_code_blocks() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "```\n# not a heading\n```\n# Heading";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["heading"], result);
+    }
+
     #[test]
     fn gfm_checkbox_not_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "- [x] checked task\n- [ ] unchecked task";
         let result = le.find_links(input);
         // GitHub-flavored markdown task list checkboxes should NOT be treated as links
@@ -510,11 +1234,318 @@ mod tests {
 
     #[test]
     fn gfm_checkbox_with_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "- [x] [actual link](http://example.com/)\n- [ ] unchecked task";
         let result = le.find_links(input);
         // Only the actual link should be detected, not the checkboxes
         assert_eq!(1, result.len());
         assert_eq!(result[0].as_ref().unwrap().target, "http://example.com/");
     }
+
+    fn broken_footnote(
+        reference: &str,
+        line: usize,
+        column: usize,
+        error: &str,
+    ) -> Result<MarkupLink, BrokenExtractedLink> {
+        Err(BrokenExtractedLink {
+            source: "".to_string(),
+            reference: reference.to_string(),
+            line,
+            column,
+            error: error.to_string(),
+        })
+    }
+
+    #[test]
+    fn footnotes() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "Text[^1], [^Note] and [^missing].\n\n[^1]: See [link](https://example.com)\n    and [file](./file.md).\n[^note]: Note.\n[^unused]: Unused [link](https://unused.com).";
+        let result = le.find_links(input);
+        let link = |target: &str, line: usize, column: usize| {
+            Ok(MarkupLink {
+                target: target.to_string(),
+                line,
+                column,
+                source: "".to_string(),
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+            })
+        };
+        assert_eq!(
+            vec![
+                link("https://example.com", 3, 11),
+                link("./file.md", 4, 9),
+                link("https://unused.com", 6, 19),
+                broken_footnote("^missing", 1, 23, "Footnote definition not found"),
+                broken_footnote("^unused", 6, 1, "Footnote is never referenced"),
+            ],
+            result
+        );
+    }
+
+    #[test_case("[^two words]")]
+    #[test_case("\\[^escaped]")]
+    #[test_case("`[^code]`")]
+    fn no_footnote_reference(input: &str) {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn ignore_footnotes() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "[^missing] <!-- mlc-disable-line -->\n\n<!-- mlc-disable-next-line -->\n[^unused]: Unused";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    fn autolink_targets(input: &str) -> Vec<String> {
+        let le = MarkdownLinkExtractor {
+            autolink_literals: true,
+            ..Default::default()
+        };
+        le.find_links(input)
+            .into_iter()
+            .map(|link| link.unwrap().target)
+            .collect()
+    }
+
+    #[test_case("www.commonmark.org", "http://www.commonmark.org")]
+    #[test_case(
+        "Visit www.commonmark.org/help for more information.",
+        "http://www.commonmark.org/help"
+    )]
+    #[test_case("Visit www.commonmark.org.", "http://www.commonmark.org")]
+    #[test_case("Visit www.commonmark.org/a.b.", "http://www.commonmark.org/a.b")]
+    #[test_case(
+        "www.google.com/search?q=Markup+(business)))",
+        "http://www.google.com/search?q=Markup+(business)"
+    )]
+    #[test_case(
+        "(www.google.com/search?q=Markup+(business))",
+        "http://www.google.com/search?q=Markup+(business)"
+    )]
+    #[test_case(
+        "www.google.com/search?q=(business))+ok",
+        "http://www.google.com/search?q=(business))+ok"
+    )]
+    #[test_case(
+        "www.google.com/search?q=commonmark&hl;",
+        "http://www.google.com/search?q=commonmark"
+    )]
+    #[test_case("www.commonmark.org/he<lp", "http://www.commonmark.org/he")]
+    #[test_case(
+        "(Visit https://encrypted.google.com/search?q=Markup+(business))",
+        "https://encrypted.google.com/search?q=Markup+(business)"
+    )]
+    #[test_case("See *https://example.com/a_b*, ok?", "https://example.com/a_b")]
+    #[test_case("Local http://localhost:8080/path!", "http://localhost:8080/path")]
+    fn autolink_literal(input: &str, expected: &str) {
+        assert_eq!(vec![expected.to_string()], autolink_targets(input));
+    }
+
+    #[test_case("Anonymous FTP is available at ftp://foo.bar.baz.")]
+    #[test_case("www. example")]
+    #[test_case("www.xxx.yyy._zzz")]
+    #[test_case("foohttps://example.com")]
+    #[test_case("`https://example.com`")]
+    #[test_case("```\nhttps://example.com\n```")]
+    #[test_case("<!-- https://example.com -->")]
+    fn no_autolink_literal(input: &str) {
+        let targets = autolink_targets(input);
+        assert!(targets.is_empty(), "{:?}", targets);
+    }
+
+    #[test]
+    fn autolink_literals_positions() {
+        let le = MarkdownLinkExtractor {
+            autolink_literals: true,
+            ..Default::default()
+        };
+        let input = "Über https://a.com and [https://b.com](https://c.com)\n- www.d.com <https://e.com>\n\nhttps://f.com <!-- mlc-disable-line -->";
+        let result = le.find_links(input);
+        let link = |target: &str, line: usize, column: usize| {
+            Ok(MarkupLink {
+                target: target.to_string(),
+                line,
+                column,
+                source: "".to_string(),
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+            })
+        };
+        assert_eq!(
+            vec![
+                link("https://a.com", 1, 6),
+                link("https://c.com", 1, 24),
+                link("http://www.d.com", 2, 3),
+                link("https://e.com", 2, 13),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn autolink_literals_disabled() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("See https://example.com");
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn code_block_urls() {
+        let le = MarkdownLinkExtractor {
+            code_block_languages: vec!["sh".to_string(), "YAML".to_string()],
+            ..Default::default()
+        };
+        let input = "```sh\ncurl -L https://example.com/install.sh | sh\ngit clone \"https://github.com/a/b.git\".\n```\n\n```yaml title=\"x\"\nurl: https://example.com/${VERSION}/x\nhome: (https://example.com/home)\n```\n\n```rust\nlet url = \"https://rust.com\";\n```\n\n    https://indented.com\n\nText `https://inline.com`";
+        let result = le.find_links(input);
+        let link = |target: &str, line: usize, column: usize| {
+            Ok(MarkupLink {
+                target: target.to_string(),
+                line,
+                column,
+                source: "".to_string(),
+                location: None,
+                base: None,
+                from_code: true,
+                wiki_link: false,
+            })
+        };
+        assert_eq!(
+            vec![
+                link("https://example.com/install.sh", 2, 9),
+                link("https://github.com/a/b.git", 3, 12),
+                link("https://example.com/home", 8, 8),
+            ],
+            result
+        );
+        assert!(result.iter().all(|link| link.as_ref().unwrap().from_code));
+    }
+
+    #[test]
+    fn code_block_urls_disabled() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("```sh\ncurl -L https://example.com/install.sh\n```");
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn front_matter_is_ignored() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "---\ntitle: [link](http://example.com)\nimage: /img.png\n---\n[link](http://example.net)";
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            target: "http://example.net".to_string(),
+            line: 5,
+            column: 1,
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        };
+        assert_eq!(vec![Ok(expected)], result);
+    }
+
+    #[test]
+    fn front_matter_no_setext_heading() {
+        let le = MarkdownLinkExtractor::default();
+        let anchors = le
+            .find_anchors("---\ntitle: Text\n---\n# Heading", SlugStyle::default())
+            .unwrap();
+        assert_eq!(vec!["heading".to_string()], anchors);
+    }
+
+    #[test]
+    fn front_matter_keys_yaml() {
+        let le = MarkdownLinkExtractor {
+            front_matter_keys: vec!["image".to_string(), "redirect_from".to_string()],
+            ..Default::default()
+        };
+        let input = "---\ntitle: A title\nimage: \"/img.png\"\nredirect_from:\n  - /old/page.html\n  - not a link\ncanonical: https://example.com\n---\nText";
+        let targets: Vec<(String, usize, usize)> = le
+            .find_links(input)
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                (link.target, link.line, link.column)
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("/img.png".to_string(), 3, 9),
+                ("/old/page.html".to_string(), 5, 5)
+            ],
+            targets
+        );
+    }
+
+    #[test]
+    fn front_matter_keys_toml() {
+        let le = MarkdownLinkExtractor {
+            front_matter_keys: vec!["links".to_string()],
+            ..Default::default()
+        };
+        let input = "+++\ntitle = \"Title\"\n[links]\nhome = 'https://example.com'\n+++\n";
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            target: "https://example.com".to_string(),
+            line: 4,
+            column: 9,
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        };
+        assert_eq!(vec![Ok(expected)], result);
+    }
+
+    #[test]
+    fn wiki_links() {
+        let le = MarkdownLinkExtractor {
+            wiki_links: true,
+            ..Default::default()
+        };
+        let input =
+            "See [[Page]] and [[Other Page#Heading|alias]].\n\n![[diagram.png]] [link](file.md)";
+        let links: Vec<(String, usize, usize, bool)> = le
+            .find_links(input)
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                (link.target, link.line, link.column, link.wiki_link)
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("Page".to_string(), 1, 5, true),
+                ("Other Page#Heading".to_string(), 1, 18, true),
+                ("diagram.png".to_string(), 3, 1, true),
+                ("file.md".to_string(), 3, 18, false),
+            ],
+            links
+        );
+    }
+
+    #[test]
+    fn wiki_links_disabled() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("See [[Page]]");
+        assert!(
+            result.iter().all(|link| match link {
+                Ok(link) => !link.wiki_link,
+                Err(_) => true,
+            }),
+            "{:?}",
+            result
+        );
+    }
 }
@@ -0,0 +1,532 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use regex::Regex;
+use std::ops::Range;
+
+use super::ignore_comments::IgnoreRegions;
+use super::link_extractor::BrokenExtractedLink;
+use super::markdown_link_extractor::MarkdownLinkExtractor;
+use super::slug::SlugStyle;
+
+/// Link extractor for MDX documents.
+///
+/// ESM `import`/`export` blocks, JSX expressions and JSX elements are blanked
+/// before the markdown is parsed, so the byte offsets of the remaining text do
+/// not change. The `href`, `src` and `to` props of JSX elements and imports of
+/// local `.md`/`.mdx` files are extracted as links.
+#[derive(Default)]
+pub struct MdxLinkExtractor {
+    /// Extractor of the markdown which remains after blanking ESM and JSX
+    pub markdown: MarkdownLinkExtractor,
+}
+
+/// Props of JSX elements which contain links
+const LINK_PROPS: [&str; 3] = ["href", "src", "to"];
+
+impl LinkExtractor for MdxLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        let document = Document::parse(text);
+        let ignore_regions = IgnoreRegions::from_text(&document.ignore_comments(text));
+
+        let mut result = self.markdown.find_links(&document.markdown);
+        for link in &mut result {
+            match link {
+                Ok(link) => {
+                    (link.line, link.column) = document.position(text, link.line, link.column);
+                }
+                Err(broken) => {
+                    (broken.line, broken.column) =
+                        document.position(text, broken.line, broken.column);
+                }
+            }
+        }
+        result.extend(document.links.iter().map(|(idx, target)| {
+            let (line, column) = line_column(text, *idx);
+            Ok(MarkupLink {
+                source: String::new(),
+                target: target.clone(),
+                line,
+                column,
+                location: None,
+                base: None,
+                from_code: false,
+                wiki_link: false,
+            })
+        }));
+        result.retain(|link| {
+            let line = match link {
+                Ok(link) => link.line,
+                Err(broken) => broken.line,
+            };
+            !ignore_regions.is_line_ignored(line)
+        });
+        result.sort_by_key(|link| match link {
+            Ok(link) => (link.line, link.column),
+            Err(broken) => (broken.line, broken.column),
+        });
+        result
+    }
+
+    fn find_anchors(&self, text: &str, slug_style: SlugStyle) -> Option<Vec<String>> {
+        self.markdown
+            .find_anchors(&Document::parse(text).headings_text(text), slug_style)
+    }
+}
+
+struct Document {
+    /// Text with blanked ESM and JSX
+    markdown: String,
+    /// Byte offset and target of links found in ESM and JSX
+    links: Vec<(usize, String)>,
+    /// Byte ranges of the blanked ESM and JSX
+    blanked: Vec<Range<usize>>,
+}
+
+impl Document {
+    fn parse(text: &str) -> Self {
+        let mut document = Document {
+            markdown: text.to_string(),
+            links: vec![],
+            blanked: vec![],
+        };
+        let code_blocks = document.blank_esm(text);
+        document.blank_jsx(text, &code_blocks);
+        document.links.sort_by_key(|(idx, _)| *idx);
+        document
+    }
+
+    /// Replace all characters except line breaks in the range with spaces
+    fn blank(&mut self, range: Range<usize>) {
+        let mut blanked = String::with_capacity(range.len());
+        for c in self.markdown[range.clone()].chars() {
+            match c {
+                '\n' | '\r' => blanked.push(c),
+                // Multi byte characters become several spaces to keep the offsets
+                c => blanked.extend(std::iter::repeat_n(' ', c.len_utf8())),
+            }
+        }
+        self.markdown.replace_range(range.clone(), &blanked);
+        self.blanked.push(range);
+    }
+
+    /// Line and column in the text of a line and column in the blanked
+    /// markdown. Blanked multi byte characters take several columns in the
+    /// markdown.
+    fn position(&self, text: &str, line: usize, column: usize) -> (usize, usize) {
+        let mut lines = self.markdown.split_inclusive('\n');
+        let line_start: usize = lines.by_ref().take(line - 1).map(str::len).sum();
+        let mut idx = line_start
+            + lines
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .take(column - 1)
+                .map(char::len_utf8)
+                .sum::<usize>();
+        while !text.is_char_boundary(idx) {
+            idx -= 1;
+        }
+        line_column(text, idx)
+    }
+
+    /// Text with removed ESM and JSX. The text of headings like
+    /// `## Title <Badge>new</Badge>` is the same as in the rendered page.
+    fn headings_text(&self, text: &str) -> String {
+        let mut blanked = self.blanked.clone();
+        blanked.sort_by_key(|range| range.start);
+        let mut result = String::new();
+        let mut last = 0;
+        for range in blanked {
+            result.push_str(&text[last..range.start]);
+            result.extend(text[range.clone()].chars().filter(|c| *c == '\n'));
+            last = range.end;
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+
+    /// Blank top level `import` and `export` statements which continue until
+    /// the next blank line. Returns the byte ranges of fenced code blocks.
+    fn blank_esm(&mut self, text: &str) -> Vec<Range<usize>> {
+        lazy_static! {
+            static ref FENCE: Regex = Regex::new(r"^ {0,3}(`{3,}|~{3,})").unwrap();
+            static ref ESM: Regex = Regex::new(r"^(?:import|export)\b").unwrap();
+            static ref MODULE: Regex =
+                Regex::new(r#"(?:\bfrom|\bimport)\s*(?:"([^"\n]+)"|'([^'\n]+)')"#).unwrap();
+        }
+
+        let mut code_blocks = vec![];
+        let mut fence: Option<(String, usize)> = None;
+        let mut esm: Option<usize> = None;
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            if let Some(esm_start) = esm {
+                if line.trim().is_empty() {
+                    self.esm_block(esm_start..start, text, &MODULE);
+                    esm = None;
+                }
+                continue;
+            }
+            if let Some((marker, fence_start)) = &fence {
+                if line.trim_start().starts_with(marker.as_str())
+                    && line.trim().chars().all(|c| marker.starts_with(c))
+                {
+                    code_blocks.push(*fence_start..offset);
+                    fence = None;
+                }
+                continue;
+            }
+            if let Some(marker) = FENCE.captures(line) {
+                fence = Some((marker[1].to_string(), start));
+            } else if ESM.is_match(line) {
+                esm = Some(start);
+            }
+        }
+        if let Some(esm_start) = esm {
+            self.esm_block(esm_start..text.len(), text, &MODULE);
+        }
+        if let Some((_, fence_start)) = fence {
+            code_blocks.push(fence_start..text.len());
+        }
+        code_blocks
+    }
+
+    fn esm_block(&mut self, range: Range<usize>, text: &str, module: &Regex) {
+        for import in module.captures_iter(&text[range.clone()]) {
+            let path = import.get(1).or_else(|| import.get(2)).unwrap();
+            let is_local = path.as_str().starts_with("./") || path.as_str().starts_with("../");
+            let is_markdown = path.as_str().ends_with(".md") || path.as_str().ends_with(".mdx");
+            if is_local && is_markdown {
+                self.links
+                    .push((range.start + path.start(), path.as_str().to_string()));
+            }
+        }
+        self.blank(range);
+    }
+
+    /// Blank JSX expressions and elements outside of code and ESM
+    fn blank_jsx(&mut self, text: &str, code_blocks: &[Range<usize>]) {
+        let bytes = text.as_bytes();
+        let skipped: Vec<Range<usize>> = code_blocks
+            .iter()
+            .chain(self.blanked.iter())
+            .cloned()
+            .collect();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if let Some(block) = skipped.iter().find(|block| block.contains(&idx)) {
+                idx = block.end;
+                continue;
+            }
+            let end = match bytes[idx] {
+                b'`' => code_span_end(bytes, idx),
+                b'{' => expression_end(bytes, idx).filter(|end| {
+                    // Keep heading ids like `{#custom-id}`
+                    bytes.get(idx + 1) != Some(&b'#') && {
+                        self.blank(idx..*end);
+                        true
+                    }
+                }),
+                b'<' => match parse_element(text, idx) {
+                    Some((end, links)) => {
+                        self.links.extend(links);
+                        self.blank(idx..end);
+                        Some(end)
+                    }
+                    None => None,
+                },
+                _ => None,
+            };
+            idx = end.unwrap_or(idx + 1);
+        }
+    }
+
+    /// Text with MDX comments like `{/* mlc-disable */}` written as html comments
+    fn ignore_comments(&self, text: &str) -> String {
+        lazy_static! {
+            static ref MDX_COMMENT: Regex = Regex::new(r"\{/\*\s*(mlc-[a-z-]+)\s*\*/\}").unwrap();
+        }
+        MDX_COMMENT.replace_all(text, "<!-- $1 -->").to_string()
+    }
+}
+
+/// End of an inline code span starting at `start`
+fn code_span_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let ticks = bytes[start..].iter().take_while(|b| **b == b'`').count();
+    let mut idx = start + ticks;
+    while idx < bytes.len() {
+        if bytes[idx] == b'`' {
+            let run = bytes[idx..].iter().take_while(|b| **b == b'`').count();
+            if run == ticks {
+                return Some(idx + run);
+            }
+            idx += run;
+        } else {
+            idx += 1;
+        }
+    }
+    // Unmatched backticks are literal text
+    Some(start + ticks)
+}
+
+/// End of the JS expression in braces starting at `start`
+fn expression_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut idx = start;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => idx = string_end(bytes, idx, quote)? - 1,
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// End of the JS string literal starting at `start`
+fn string_end(bytes: &[u8], start: usize, quote: u8) -> Option<usize> {
+    let mut idx = start + 1;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' => idx += 1,
+            b if b == quote => return Some(idx + 1),
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// Parse the JSX element tag starting at `start`. Returns the end of the tag
+/// and the links found in its props.
+fn parse_element(text: &str, start: usize) -> Option<(usize, Vec<(usize, String)>)> {
+    let bytes = text.as_bytes();
+    let mut idx = start + 1;
+    if bytes.get(idx) == Some(&b'/') {
+        idx += 1;
+    }
+    // Fragments `<>` and `</>`
+    if bytes.get(idx) == Some(&b'>') {
+        return Some((idx + 1, vec![]));
+    }
+    if !bytes.get(idx)?.is_ascii_alphabetic() {
+        return None;
+    }
+    while bytes
+        .get(idx)
+        .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+    {
+        idx += 1;
+    }
+    // Autolinks like `<https://example.com>` are no elements
+    if !matches!(bytes.get(idx)?, b'>' | b'/' | b' ' | b'\t' | b'\n' | b'\r') {
+        return None;
+    }
+
+    let mut links = vec![];
+    loop {
+        while bytes.get(idx)?.is_ascii_whitespace() {
+            idx += 1;
+        }
+        match bytes[idx] {
+            b'>' => return Some((idx + 1, links)),
+            b'/' if bytes.get(idx + 1) == Some(&b'>') => return Some((idx + 2, links)),
+            b'{' => idx = expression_end(bytes, idx)?,
+            _ => {
+                let name_start = idx;
+                while bytes
+                    .get(idx)
+                    .is_some_and(|b| !b.is_ascii_whitespace() && !b"=/>{".contains(b))
+                {
+                    idx += 1;
+                }
+                if idx == name_start {
+                    return None;
+                }
+                let name = &text[name_start..idx];
+                while bytes.get(idx)?.is_ascii_whitespace() {
+                    idx += 1;
+                }
+                if bytes[idx] != b'=' {
+                    continue;
+                }
+                idx += 1;
+                while bytes.get(idx)?.is_ascii_whitespace() {
+                    idx += 1;
+                }
+                let value_start = idx;
+                let value = match bytes[idx] {
+                    quote @ (b'"' | b'\'') => {
+                        idx = string_end(bytes, idx, quote)?;
+                        Some(&text[value_start + 1..idx - 1])
+                    }
+                    b'{' => {
+                        idx = expression_end(bytes, idx)?;
+                        string_literal(text[value_start + 1..idx - 1].trim())
+                    }
+                    _ => return None,
+                };
+                if let Some(value) = value.filter(|_| LINK_PROPS.contains(&name)) {
+                    links.push((name_start, value.to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// Content of a JS string literal without interpolation
+fn string_literal(expression: &str) -> Option<&str> {
+    let quote = expression.chars().next()?;
+    if !matches!(quote, '"' | '\'' | '`') || expression.len() < 2 || !expression.ends_with(quote) {
+        return None;
+    }
+    let content = &expression[1..expression.len() - 1];
+    if content.contains(quote) || content.contains("${") {
+        return None;
+    }
+    Some(content)
+}
+
+/// Line and column (1-indexed) of the byte offset
+fn line_column(text: &str, idx: usize) -> (usize, usize) {
+    let before = &text[..idx];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(target: &str, line: usize, column: usize) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            source: "".to_string(),
+            target: target.to_string(),
+            line,
+            column,
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        })
+    }
+
+    #[test]
+    fn no_link() {
+        let le = MdxLinkExtractor::default();
+        let input =
+            "# Title\n\nSome {props.value} and <Tabs>\n  <Tab label=\"a\">text</Tab>\n</Tabs>";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn markdown_options() {
+        let le = MdxLinkExtractor {
+            markdown: MarkdownLinkExtractor {
+                autolink_literals: true,
+                ..MarkdownLinkExtractor::default()
+            },
+        };
+        let input = "See <Badge /> https://bare.com";
+        assert_eq!(vec![link("https://bare.com", 1, 15)], le.find_links(input));
+        assert!(MdxLinkExtractor::default().find_links(input).is_empty());
+    }
+
+    #[test]
+    fn multi_byte_jsx_attribute() {
+        let le = MdxLinkExtractor::default();
+        let input = "<Card\n  title=\"über\"\n/> [x](./y.md)";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("./y.md", 3, 4)], result);
+    }
+
+    #[test]
+    fn multi_byte_jsx_before_link() {
+        let le = MdxLinkExtractor::default();
+        let input = "Text <B t=\"ü\" /> [x](./y.md)";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("./y.md", 1, 18)], result);
+    }
+
+    #[test]
+    fn esm_blocks() {
+        let le = MdxLinkExtractor::default();
+        let input = "import Intro from './intro.mdx';\nimport {Card} from \"@site/src/components\";\nimport Guide from '../guide.md'\n\nexport const meta = {\n  href: 'https://ignored.com',\n  link: <Link to=\"/in-esm\" />,\n};\n\n[Link](https://example.com)";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("./intro.mdx", 1, 20),
+                link("../guide.md", 3, 20),
+                link("https://example.com", 10, 1),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn jsx_props() {
+        let le = MdxLinkExtractor::default();
+        let input = "<Link to=\"/docs/x\">Docs</Link> and <Card\n  title={`Card`}\n  href={'https://card.com'}\n  icon={<Icon />}\n/>\n\n<img src='./img.png' alt=\"a > b\" /> <a href={url}>dynamic</a>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("/docs/x", 1, 7),
+                link("https://card.com", 3, 3),
+                link("./img.png", 7, 6),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn expressions_and_markdown() {
+        let le = MdxLinkExtractor::default();
+        let input = "Text {'[not](https://expr.com)'} and [link](./file.md).\n\n{/* [comment](https://comment.com) */}\n\n![image](./image.png) {\n  nested({a: '}'})\n} <https://autolink.com>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("./file.md", 1, 38),
+                link("./image.png", 5, 1),
+                link("https://autolink.com", 7, 3),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn code_is_kept() {
+        let le = MdxLinkExtractor::default();
+        let input = "```jsx\nimport X from './x.md'\n<Link to=\"/in-code\" />\n```\n\nInline `<Link to=\"/code\" />` code.";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn ignore_comments() {
+        let le = MdxLinkExtractor::default();
+        let input = "{/* mlc-disable-next-line */}\n<Link to=\"/ignored\" />\n[link](./kept.md) {/* mlc-disable-line */}\n[next](./next.md)";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("./next.md", 4, 1)], result);
+    }
+
+    #[test]
+    fn anchors() {
+        let le = MdxLinkExtractor::default();
+        let input = "import X from './x.mdx'\n\n## Hello <Badge>new</Badge> {props.x}\n\n## Custom {#custom-id}";
+        let result = le.find_anchors(input, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["hello-new", "custom-id"], result);
+    }
+}
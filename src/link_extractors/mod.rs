@@ -1,4 +1,16 @@
-mod html_link_extractor;
+mod asciidoc_link_extractor;
+mod front_matter;
+pub(crate) mod html_link_extractor;
 mod ignore_comments;
+mod json;
+mod latex_link_extractor;
 pub mod link_extractor;
 mod markdown_link_extractor;
+mod mdx_link_extractor;
+mod notebook_link_extractor;
+mod rst_link_extractor;
+mod rust_doc_link_extractor;
+pub mod slug;
+pub(crate) mod source_code_link_extractor;
+mod structured_data;
+pub mod structured_data_link_extractor;
@@ -0,0 +1,242 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+
+use super::html_link_extractor::HtmlLinkExtractor;
+use super::json::{Json, JsonString, Parser};
+use super::link_extractor::BrokenExtractedLink;
+use super::markdown_link_extractor::MarkdownLinkExtractor;
+use super::slug::SlugStyle;
+
+/// Link extractor for Jupyter notebooks.
+///
+/// Links of `markdown` cells and of the html of `display_data` outputs are
+/// extracted. Line and column point into the raw JSON file, the location of a
+/// link names the (1-indexed) cell and the line within the cell.
+#[derive(Default)]
+pub struct NotebookLinkExtractor {
+    /// Extractor of the markdown cells
+    pub markdown: MarkdownLinkExtractor,
+}
+
+impl LinkExtractor for NotebookLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        let cells = match parse_cells(text) {
+            Some(cells) => cells,
+            None => return vec![],
+        };
+
+        let mut result = vec![];
+        for (idx, cell) in cells.iter().enumerate() {
+            let cell_num = idx + 1;
+            match cell.get("cell_type").and_then(Json::as_str) {
+                Some("markdown") => {
+                    if let Some(source) = cell.get("source").and_then(Json::text) {
+                        let links = self.markdown.find_links(&source.value);
+                        result.extend(map_links(links, &source, &format!("cell {cell_num}")));
+                    }
+                }
+                Some("code") => {
+                    let outputs = cell.get("outputs").map_or(&[][..], Json::as_array);
+                    for (output_idx, output) in outputs.iter().enumerate() {
+                        if output.get("output_type").and_then(Json::as_str) != Some("display_data")
+                        {
+                            continue;
+                        }
+                        if let Some(html) = output
+                            .get("data")
+                            .and_then(|data| data.get("text/html"))
+                            .and_then(Json::text)
+                        {
+                            let links = HtmlLinkExtractor().find_links(&html.value);
+                            let location = format!("cell {cell_num}, output {}", output_idx + 1);
+                            result.extend(map_links(links, &html, &location));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    fn find_anchors(&self, text: &str, slug_style: SlugStyle) -> Option<Vec<String>> {
+        let cells = parse_cells(text)?;
+        // Headings of all cells share one namespace, like in the rendered notebook
+        let markdown: Vec<String> = cells
+            .iter()
+            .filter(|cell| cell.get("cell_type").and_then(Json::as_str) == Some("markdown"))
+            .filter_map(|cell| cell.get("source").and_then(Json::text))
+            .map(|source| source.value)
+            .collect();
+        self.markdown
+            .find_anchors(&markdown.join("\n\n"), slug_style)
+    }
+}
+
+fn parse_cells(text: &str) -> Option<Vec<Json>> {
+    let notebook = match Parser::new(text).parse() {
+        Ok(notebook) => notebook,
+        Err(e) => {
+            warn!("Failed to parse notebook. {e}");
+            return None;
+        }
+    };
+    match notebook.get("cells") {
+        Some(Json::Array(cells)) => Some(cells.clone()),
+        _ => {
+            warn!("Notebook does not contain any cells.");
+            None
+        }
+    }
+}
+
+/// Map the positions of links found in a cell to the raw JSON file
+fn map_links(
+    links: Vec<Result<MarkupLink, BrokenExtractedLink>>,
+    cell: &JsonString,
+    location: &str,
+) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+    links
+        .into_iter()
+        .map(|link| match link {
+            Ok(mut link) => {
+                link.location = Some(format!("{location}, line {}", link.line));
+                (link.line, link.column) = cell.raw_position(link.line, link.column);
+                Ok(link)
+            }
+            Err(mut broken) => {
+                broken.error = format!("{} ({location}, line {})", broken.error, broken.line);
+                (broken.line, broken.column) = cell.raw_position(broken.line, broken.column);
+                Err(broken)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": [
+    "# Intro\n",
+    "\n",
+    "See [the \"docs\"](https://example.com/docs) and [local](./data.csv)."
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {},
+   "outputs": [
+    {
+     "output_type": "stream",
+     "text": ["<a href=\"https://stream.com\">no</a>"]
+    },
+    {
+     "output_type": "display_data",
+     "data": {
+      "text/html": ["<p>\n", "  <a href=\"https://output.com\">Output</a>\n", "</p>"],
+      "text/plain": ["https://plain.com"]
+     },
+     "metadata": {}
+    }
+   ],
+   "source": ["print('[not](https://code.com)')"]
+  },
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": "Single über string [link](https://single.com)\n[broken][ref]"
+  }
+ ],
+ "metadata": {},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"##;
+
+    fn link(
+        target: &str,
+        line: usize,
+        column: usize,
+        location: &str,
+    ) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            source: "".to_string(),
+            target: target.to_string(),
+            line,
+            column,
+            location: Some(location.to_string()),
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        })
+    }
+
+    #[test]
+    fn links_in_cells() {
+        let le = NotebookLinkExtractor::default();
+        let result = le.find_links(NOTEBOOK);
+        assert_eq!(
+            vec![
+                link("https://example.com/docs", 9, 10, "cell 1, line 3"),
+                link("./data.csv", 9, 55, "cell 1, line 3"),
+                link("https://output.com", 24, 43, "cell 2, output 2, line 2"),
+                Err(BrokenExtractedLink {
+                    source: "".to_string(),
+                    reference: "ref".to_string(),
+                    line: 35,
+                    column: 62,
+                    error: "Markdown reference not found (cell 3, line 2)".to_string(),
+                }),
+                link("https://single.com", 35, 34, "cell 3, line 1"),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn markdown_options() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor {
+                autolink_literals: true,
+                ..MarkdownLinkExtractor::default()
+            },
+        };
+        let notebook =
+            r#"{"cells": [{"cell_type": "markdown", "source": ["See https://bare.com"]}]}"#;
+        let result = le.find_links(notebook);
+        assert_eq!(
+            vec![link("https://bare.com", 1, 54, "cell 1, line 1")],
+            result
+        );
+        assert!(NotebookLinkExtractor::default()
+            .find_links(notebook)
+            .is_empty());
+    }
+
+    #[test]
+    fn anchors() {
+        let le = NotebookLinkExtractor::default();
+        let result = le.find_anchors(NOTEBOOK, SlugStyle::GitHub).unwrap();
+        assert_eq!(vec!["intro"], result);
+    }
+
+    #[test]
+    fn invalid_json() {
+        let le = NotebookLinkExtractor::default();
+        assert!(le.find_links("{\"cells\": [").is_empty());
+        assert!(le.find_anchors("no json", SlugStyle::GitHub).is_none());
+    }
+
+    #[test]
+    fn deeply_nested_json() {
+        let le = NotebookLinkExtractor::default();
+        let notebook = format!("{{\"cells\": {}", "[".repeat(100_000));
+        assert!(le.find_links(&notebook).is_empty());
+    }
+}
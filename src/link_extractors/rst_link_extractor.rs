@@ -0,0 +1,615 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::link_extractor::BrokenExtractedLink;
+
+/// Link extractor for reStructuredText documents.
+///
+/// Supports inline hyperlinks (`` `text <url>`_ ``), named and anonymous
+/// hyperlink targets and references, the `image` and `figure` directives and
+/// the `:doc:` role. Labels of `:ref:` roles are global in a Sphinx project and
+/// can not be resolved within one document, so they are not checked.
+pub struct RstLinkExtractor();
+
+/// Position of an element in the document (1-indexed)
+#[derive(Clone, Copy, Debug)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+/// Target of a named hyperlink reference
+#[derive(Debug)]
+enum NamedTarget {
+    /// External URL or file path
+    Uri(String),
+    /// Reference to another named target (`.. _a: b_`)
+    Indirect(String),
+    /// Internal target (`.. _name:`) or section title
+    Internal,
+}
+
+#[derive(Debug)]
+enum Reference {
+    Named(String),
+    Anonymous,
+}
+
+/// Lines of a block which shall not be scanned for links, like literal blocks
+/// or comments. All lines indented deeper than `indent` belong to the block.
+struct SkipBlock {
+    indent: usize,
+}
+
+#[derive(Default)]
+struct Document {
+    links: Vec<(Position, String)>,
+    named_targets: HashMap<String, NamedTarget>,
+    anonymous_targets: Vec<String>,
+    references: Vec<(Position, Reference)>,
+}
+
+impl Document {
+    fn add_named_target(&mut self, name: &str, target: NamedTarget) {
+        self.named_targets
+            .entry(normalize_name(name))
+            .or_insert(target);
+    }
+
+    fn add_target(&mut self, name: Option<&str>, uri: &str) {
+        let target = if uri.is_empty() {
+            NamedTarget::Internal
+        } else if let Some(reference) = indirect_reference(uri) {
+            NamedTarget::Indirect(reference)
+        } else {
+            NamedTarget::Uri(uri.to_string())
+        };
+        match name {
+            Some(name) => self.add_named_target(name, target),
+            None => self.anonymous_targets.push(uri.to_string()),
+        }
+    }
+
+    /// Resolve a named reference. Follows indirect targets.
+    fn resolve(&self, name: &str) -> Option<Option<&str>> {
+        let mut name = normalize_name(name);
+        // Limit the depth to not loop forever on circular references
+        for _ in 0..10 {
+            match self.named_targets.get(&name)? {
+                NamedTarget::Uri(uri) => return Some(Some(uri)),
+                NamedTarget::Internal => return Some(None),
+                NamedTarget::Indirect(reference) => name = normalize_name(reference),
+            }
+        }
+        None
+    }
+}
+
+impl LinkExtractor for RstLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        lazy_static! {
+            static ref EXPLICIT_MARKUP: Regex = Regex::new(r"^(\s*)\.\.(?:\s+(.*))?$").unwrap();
+            static ref NAMED_TARGET: Regex =
+                Regex::new(r"^_(`[^`]+`|[^:`]+):(?:\s+(.*))?$").unwrap();
+            static ref ANONYMOUS_TARGET: Regex = Regex::new(r"^__:(?:\s+(.*))?$").unwrap();
+            static ref SHORT_ANONYMOUS_TARGET: Regex = Regex::new(r"^(\s*)__\s+(\S.*)$").unwrap();
+            static ref DIRECTIVE: Regex =
+                Regex::new(r"^([[:alnum:]][[:alnum:]_+:.-]*)::(?:\s+(.*))?$").unwrap();
+            static ref TARGET_OPTION: Regex = Regex::new(r"^(\s*):target:\s+(\S+)").unwrap();
+            static ref SUBSTITUTION_DEFINITION: Regex =
+                Regex::new(r"^\|[^|\s](?:[^|]*[^|\s])?\|\s+(.*)$").unwrap();
+        }
+
+        let lines: Vec<&str> = text.lines().collect();
+        let mut doc = Document::default();
+        let mut skip_block: Option<SkipBlock> = None;
+        let mut image_directive: Option<usize> = None;
+
+        for (idx, line) in lines.iter().enumerate() {
+            let line_num = idx + 1;
+            let indent = line.len() - line.trim_start().len();
+
+            if let Some(block) = &skip_block {
+                if line.trim().is_empty() || indent > block.indent {
+                    continue;
+                }
+                skip_block = None;
+            }
+            if let Some(directive_indent) = image_directive {
+                // Options follow the directive directly, the caption of a figure may contain links
+                if indent > directive_indent && line.trim_start().starts_with(':') {
+                    if let Some(option) = TARGET_OPTION.captures(line) {
+                        let uri = option.get(2).unwrap();
+                        doc.links
+                            .push((position(line, line_num, uri.start()), uri.as_str().into()));
+                    }
+                    continue;
+                }
+                image_directive = None;
+            }
+
+            // Section titles are implicit targets
+            if let Some(next) = lines.get(idx + 1) {
+                let title = line.trim();
+                if !title.is_empty()
+                    && indent == 0
+                    && is_section_adornment(next)
+                    && next.trim().chars().count() >= title.chars().count()
+                    && !is_section_adornment(line)
+                {
+                    doc.add_named_target(title, NamedTarget::Internal);
+                }
+            }
+
+            if let Some(explicit) = EXPLICIT_MARKUP.captures(line) {
+                let markup_indent = explicit.get(1).map_or(0, |m| m.as_str().len());
+                let content = explicit.get(2).map_or("", |m| m.as_str());
+                let content_start = explicit.get(2).map_or(line.len(), |m| m.start());
+                // Substitution definitions like `.. |build| image:: url` contain a directive
+                let (directive_content, directive_start) =
+                    match SUBSTITUTION_DEFINITION.captures(content) {
+                        Some(definition) => {
+                            let directive = definition.get(1).unwrap();
+                            (directive.as_str(), content_start + directive.start())
+                        }
+                        None => (content, content_start),
+                    };
+
+                if let Some(target) = ANONYMOUS_TARGET.captures(content) {
+                    let uri = target_uri(target.get(1), &lines, idx, markup_indent);
+                    doc.add_target(None, &uri);
+                    skip_block = Some(SkipBlock {
+                        indent: markup_indent,
+                    });
+                } else if let Some(target) = NAMED_TARGET.captures(content) {
+                    let name = target[1].trim_matches('`');
+                    let uri = target_uri(target.get(2), &lines, idx, markup_indent);
+                    doc.add_target(Some(name), &uri);
+                    skip_block = Some(SkipBlock {
+                        indent: markup_indent,
+                    });
+                } else if let Some(directive) = DIRECTIVE.captures(directive_content) {
+                    match &directive[1] {
+                        "image" | "figure" => {
+                            if let Some(arg) = directive.get(2) {
+                                let column = directive_start + arg.start();
+                                doc.links.push((
+                                    position(line, line_num, column),
+                                    arg.as_str().trim().to_string(),
+                                ));
+                            }
+                            image_directive = Some(markup_indent);
+                        }
+                        "code" | "code-block" | "sourcecode" | "raw" | "math" | "highlight" => {
+                            skip_block = Some(SkipBlock {
+                                indent: markup_indent,
+                            });
+                        }
+                        _ => {
+                            // Content of other directives such as notes may contain links
+                            let arguments_start = directive.get(2).map_or(0, |m| m.start());
+                            find_inline(
+                                &mut doc,
+                                line,
+                                line_num,
+                                directive_start + arguments_start,
+                            );
+                        }
+                    }
+                } else if content.starts_with('[') {
+                    // Footnotes and citations contain text with links
+                    find_inline(&mut doc, line, line_num, content_start);
+                } else {
+                    // Everything else is a comment
+                    skip_block = Some(SkipBlock {
+                        indent: markup_indent,
+                    });
+                }
+                continue;
+            }
+
+            if let Some(target) = SHORT_ANONYMOUS_TARGET.captures(line) {
+                doc.add_target(None, target[2].trim());
+                continue;
+            }
+
+            find_inline(&mut doc, line, line_num, 0);
+
+            // Paragraph ending with `::` is followed by a literal block
+            if line.trim_end().ends_with("::") {
+                skip_block = Some(SkipBlock { indent });
+            }
+        }
+
+        let mut result: Vec<Result<MarkupLink, BrokenExtractedLink>> = doc
+            .links
+            .iter()
+            .map(|(pos, target)| Ok(markup_link(*pos, target)))
+            .collect();
+
+        // Anonymous references are matched with the targets in document order
+        doc.references
+            .sort_by_key(|(pos, _)| (pos.line, pos.column));
+        let mut anonymous_targets = doc.anonymous_targets.iter();
+        for (pos, reference) in &doc.references {
+            match reference {
+                Reference::Named(name) => match doc.resolve(name) {
+                    Some(Some(uri)) => result.push(Ok(markup_link(*pos, uri))),
+                    Some(None) => {}
+                    None => result.push(Err(broken_link(*pos, name))),
+                },
+                Reference::Anonymous => match anonymous_targets.next() {
+                    Some(uri) => match indirect_reference(uri) {
+                        Some(name) => match doc.resolve(&name) {
+                            Some(Some(uri)) => result.push(Ok(markup_link(*pos, uri))),
+                            Some(None) => {}
+                            None => result.push(Err(broken_link(*pos, &name))),
+                        },
+                        None => result.push(Ok(markup_link(*pos, uri))),
+                    },
+                    None => result.push(Err(broken_link(*pos, "__"))),
+                },
+            }
+        }
+        result.sort_by_key(|r| match r {
+            Ok(link) => (link.line, link.column),
+            Err(broken) => (broken.line, broken.column),
+        });
+        result
+    }
+}
+
+/// Find inline links, references and roles in the line starting at byte `start`
+fn find_inline(doc: &mut Document, line: &str, line_num: usize, start: usize) {
+    lazy_static! {
+        static ref INLINE_LITERAL: Regex = Regex::new(r"``.+?``").unwrap();
+        static ref ROLE: Regex = Regex::new(r":([[:alnum:]_:+.-]+):`([^`]+)`").unwrap();
+        static ref EMBEDDED_URI: Regex = Regex::new(r"^(?s)(.*?)\s*<([^<>]+)>$").unwrap();
+        static ref PHRASE_REFERENCE: Regex = Regex::new(r"`([^`]+)`(__?)").unwrap();
+        static ref SUBSTITUTION_REFERENCE: Regex = Regex::new(r"\|([^|]+)\|(__?)").unwrap();
+        static ref WORD_REFERENCE: Regex = Regex::new(
+            r"(?:^|[\s(\[{<'\x22])([[:alnum:]](?:[[:alnum:]]|[-._:+][[:alnum:]])*)(__?)"
+        )
+        .unwrap();
+    }
+
+    // Blank processed parts of the line to not match them twice
+    let mut text = line[start..].to_string();
+    let blank = |text: &mut String, range: std::ops::Range<usize>| {
+        text.replace_range(range.clone(), &" ".repeat(range.len()));
+    };
+
+    for literal in INLINE_LITERAL.find_iter(&text.clone()) {
+        blank(&mut text, literal.range());
+    }
+
+    for role in ROLE.captures_iter(&text.clone()) {
+        let whole = role.get(0).unwrap();
+        let content = role.get(2).unwrap();
+        let target = match EMBEDDED_URI.captures(content.as_str()) {
+            Some(embedded) => embedded.get(2).unwrap().as_str(),
+            None => content.as_str(),
+        };
+        match &role[1] {
+            "doc" | "download" => doc.links.push((
+                position(line, line_num, start + whole.start()),
+                target.to_string(),
+            )),
+            "ref" => debug!("Skip check of :ref: role to label '{target}'"),
+            _ => {}
+        }
+        blank(&mut text, whole.range());
+    }
+
+    for phrase in PHRASE_REFERENCE.captures_iter(&text.clone()) {
+        let whole = phrase.get(0).unwrap();
+        let pos = position(line, line_num, start + whole.start());
+        let content = &phrase[1];
+        let anonymous = &phrase[2] == "__";
+        match EMBEDDED_URI.captures(content) {
+            Some(embedded) => {
+                let name = embedded[1].trim();
+                let uri = &embedded[2];
+                match indirect_reference(uri) {
+                    Some(reference) => doc.references.push((pos, Reference::Named(reference))),
+                    None => doc.links.push((pos, uri.to_string())),
+                }
+                if !anonymous && !name.is_empty() {
+                    doc.add_target(Some(name), uri);
+                }
+            }
+            None if anonymous => doc.references.push((pos, Reference::Anonymous)),
+            None => doc
+                .references
+                .push((pos, Reference::Named(content.to_string()))),
+        }
+        blank(&mut text, whole.range());
+    }
+
+    for substitution in SUBSTITUTION_REFERENCE.captures_iter(&text.clone()) {
+        let whole = substitution.get(0).unwrap();
+        let pos = position(line, line_num, start + whole.start());
+        let reference = if &substitution[2] == "__" {
+            Reference::Anonymous
+        } else {
+            Reference::Named(substitution[1].to_string())
+        };
+        doc.references.push((pos, reference));
+        blank(&mut text, whole.range());
+    }
+
+    for word in WORD_REFERENCE.captures_iter(&text) {
+        let end = word.get(0).unwrap().end();
+        let is_word_end = text[end..]
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace() || ".,;:!?)]}>'\"".contains(c));
+        if !is_word_end {
+            continue;
+        }
+        let name = word.get(1).unwrap();
+        let pos = position(line, line_num, start + name.start());
+        let reference = if &word[2] == "__" {
+            Reference::Anonymous
+        } else {
+            Reference::Named(name.as_str().to_string())
+        };
+        doc.references.push((pos, reference));
+    }
+}
+
+/// Line of repeated punctuation characters which over- or underlines a section title
+fn is_section_adornment(line: &str) -> bool {
+    let line = line.trim_end();
+    match line.chars().next() {
+        Some(first) => "=-`:'\"~^_*+#<>.".contains(first) && line.chars().all(|c| c == first),
+        None => false,
+    }
+}
+
+/// URI of a hyperlink target. Long URIs may continue on the following indented lines.
+fn target_uri(first: Option<regex::Match>, lines: &[&str], idx: usize, indent: usize) -> String {
+    let mut uri: String = first.map_or("", |m| m.as_str()).trim().to_string();
+    for line in lines.iter().skip(idx + 1) {
+        let line_indent = line.len() - line.trim_start().len();
+        if line.trim().is_empty() || line_indent <= indent {
+            break;
+        }
+        uri.push_str(line.trim());
+    }
+    // Whitespace within URIs is ignored, escaped whitespace is kept
+    uri.split_whitespace().collect::<Vec<_>>().join("")
+}
+
+/// Name of the referenced target, if the URI is a reference like `name_`
+fn indirect_reference(uri: &str) -> Option<String> {
+    let name = uri.strip_suffix('_')?;
+    if name.ends_with('\\') || name.contains("://") {
+        return None;
+    }
+    Some(name.trim_matches('`').to_string())
+}
+
+/// Reference names are whitespace-neutral and case-insensitive
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn position(line: &str, line_num: usize, byte_idx: usize) -> Position {
+    Position {
+        line: line_num,
+        column: line[..byte_idx].chars().count() + 1,
+    }
+}
+
+fn markup_link(pos: Position, target: &str) -> MarkupLink {
+    MarkupLink {
+        source: String::new(),
+        location: None,
+        base: None,
+        from_code: false,
+        wiki_link: false,
+        target: target.to_string(),
+        line: pos.line,
+        column: pos.column,
+    }
+}
+
+fn broken_link(pos: Position, reference: &str) -> BrokenExtractedLink {
+    info!(
+        "Broken reference in rst file: {} (line {}, column {})",
+        reference, pos.line, pos.column
+    );
+    BrokenExtractedLink {
+        source: String::new(),
+        reference: reference.to_string(),
+        line: pos.line,
+        column: pos.column,
+        error: "reStructuredText reference not found".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(target: &str, line: usize, column: usize) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            source: "".to_string(),
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+            target: target.to_string(),
+            line,
+            column,
+        })
+    }
+
+    #[test]
+    fn no_link() {
+        let le = RstLinkExtractor();
+        let input = "Title\n=====\n\nSome *text* with ``literal_`` and snake_case words.";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn inline_hyperlink() {
+        let le = RstLinkExtractor();
+        let input = "See `the docs <https://example.com/docs>`_ and\n`file <./other.rst>`__.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://example.com/docs", 1, 5),
+                link("./other.rst", 2, 1)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn inline_hyperlink_defines_target() {
+        let le = RstLinkExtractor();
+        let input = "`Python <https://www.python.org/>`_ is great. Use Python_ again.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://www.python.org/", 1, 1),
+                link("https://www.python.org/", 1, 51)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn named_targets() {
+        let le = RstLinkExtractor();
+        let input = "Read Python_ or `the spec`_.\n\n.. _Python: https://www.python.org/\n.. _The  Spec:\n   https://example.com/\n   spec.html";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://www.python.org/", 1, 6),
+                link("https://example.com/spec.html", 1, 17)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn indirect_targets() {
+        let le = RstLinkExtractor();
+        let input = "Use `py`_.\n\n.. _py: Python_\n.. _Python: https://www.python.org/";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("https://www.python.org/", 1, 5)], result);
+    }
+
+    #[test]
+    fn anonymous_targets() {
+        let le = RstLinkExtractor();
+        let input =
+            "First__ and `second one`__.\n\n.. __: https://first.com/\n__ https://second.com/";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://first.com/", 1, 1),
+                link("https://second.com/", 1, 13)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn internal_targets_and_sections() {
+        let le = RstLinkExtractor();
+        let input = ".. _intro:\n\nIntroduction\n============\n\nSee intro_ and `Introduction`_.";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn broken_references() {
+        let le = RstLinkExtractor();
+        let input = "Missing_ and `not there`_ and anonymous__.";
+        let result = le.find_links(input);
+        let expected: Vec<Result<MarkupLink, BrokenExtractedLink>> =
+            [("Missing", 1), ("not there", 14), ("__", 31)]
+                .iter()
+                .map(|(reference, column)| {
+                    Err(BrokenExtractedLink {
+                        source: "".to_string(),
+                        reference: reference.to_string(),
+                        line: 1,
+                        column: *column,
+                        error: "reStructuredText reference not found".to_string(),
+                    })
+                })
+                .collect();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn image_and_figure() {
+        let le = RstLinkExtractor();
+        let input = ".. image:: images/logo.png\n   :alt: Logo\n   :target: https://example.com/\n\n  .. figure:: ../diagram.svg\n\n     Caption";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("images/logo.png", 1, 12),
+                link("https://example.com/", 3, 13),
+                link("../diagram.svg", 5, 15)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn substitution_definitions() {
+        let le = RstLinkExtractor();
+        let input = "|build| |docs|\n\n.. |build| image:: https://ci.example.com/badge.svg\n   :target: https://ci.example.com/builds\n.. |docs| replace:: See `the docs <https://docs.example.com>`_\n.. |comment| no directive https://ignored.com";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://ci.example.com/badge.svg", 3, 20),
+                link("https://ci.example.com/builds", 4, 13),
+                link("https://docs.example.com", 5, 25),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn roles() {
+        let le = RstLinkExtractor();
+        let input = "See :doc:`install` and :doc:`Usage <guide/usage>` or :ref:`some-label`.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![link("install", 1, 5), link("guide/usage", 1, 24)],
+            result
+        );
+    }
+
+    #[test]
+    fn literal_blocks_and_comments() {
+        let le = RstLinkExtractor();
+        let input = "Example::\n\n    `x <http://literal.com>`_\n\n.. code-block:: rst\n\n   `y <http://code.com>`_\n\n.. This is a comment with `z <http://comment.com>`_\n   still_ comment\n\n`ok <http://ok.com>`_";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("http://ok.com", 12, 1)], result);
+    }
+
+    #[test]
+    fn note_directive_content() {
+        let le = RstLinkExtractor();
+        let input = ".. note:: Read `this <http://a.com>`_.\n\n   And `that <http://b.com>`_.";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![link("http://a.com", 1, 16), link("http://b.com", 3, 8)],
+            result
+        );
+    }
+}
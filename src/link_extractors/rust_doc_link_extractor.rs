@@ -0,0 +1,337 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use regex::Regex;
+
+use super::link_extractor::BrokenExtractedLink;
+use super::markdown_link_extractor::MarkdownLinkExtractor;
+
+/// Link extractor for the doc comments of Rust source files.
+///
+/// Contiguous `///` or `//!` lines and `/** */` or `/*! */` blocks are parsed
+/// as markdown. Intra-doc links like ``[`Vec`]`` or `[Vec](std::vec::Vec)` are
+/// resolved by rustdoc and not checked.
+#[derive(Default)]
+pub struct RustDocLinkExtractor {
+    /// Extractor of the markdown of the doc comments
+    pub markdown: MarkdownLinkExtractor,
+}
+
+impl LinkExtractor for RustDocLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        let mut result = vec![];
+        for comment in doc_comments(text) {
+            let markdown = comment
+                .lines
+                .iter()
+                .map(|line| line.content(comment.indent))
+                .collect::<Vec<_>>()
+                .join("\n");
+            for link in self.markdown.find_links(&markdown) {
+                match link {
+                    Ok(link) if is_intra_doc_link(&link.target) => {
+                        debug!("Skip intra-doc link to '{}'.", link.target);
+                    }
+                    // Shortcut and collapsed references without definition
+                    // like `[Vec]` or `[Vec][]` are resolved by rustdoc
+                    Err(broken) if is_item_path(&broken.reference) => {
+                        debug!("Skip intra-doc link to '{}'.", broken.reference);
+                    }
+                    Ok(mut link) => {
+                        (link.line, link.column) = comment.position(link.line, link.column);
+                        result.push(Ok(link));
+                    }
+                    Err(mut broken) => {
+                        (broken.line, broken.column) = comment.position(broken.line, broken.column);
+                        result.push(Err(broken));
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Line of a doc comment
+struct DocLine<'a> {
+    /// Line number in the source file (1-indexed)
+    line: usize,
+    /// Source line
+    text: &'a str,
+    /// Byte offset of the comment content in the source line
+    start: usize,
+}
+
+impl<'a> DocLine<'a> {
+    fn content(&self, indent: usize) -> &'a str {
+        let content = &self.text[self.start..];
+        &content[indent.min(content.len() - content.trim_start().len())..]
+    }
+}
+
+/// Block of contiguous doc comment lines
+struct DocComment<'a> {
+    lines: Vec<DocLine<'a>>,
+    /// Indentation shared by all lines which is removed by rustdoc
+    indent: usize,
+}
+
+impl DocComment<'_> {
+    fn new(lines: Vec<DocLine<'_>>) -> DocComment<'_> {
+        let indent = lines
+            .iter()
+            .map(|line| &line.text[line.start..])
+            .filter(|content| !content.trim().is_empty())
+            .map(|content| content.len() - content.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        DocComment { lines, indent }
+    }
+
+    /// Map a position in the markdown of the comment to the source file
+    fn position(&self, line: usize, column: usize) -> (usize, usize) {
+        match self.lines.get(line - 1) {
+            Some(doc_line) => {
+                let content = doc_line.content(self.indent);
+                let prefix = &doc_line.text[..doc_line.text.len() - content.len()];
+                (doc_line.line, prefix.chars().count() + column)
+            }
+            None => (line, column),
+        }
+    }
+}
+
+/// Find all doc comments of the Rust source
+fn doc_comments(text: &str) -> Vec<DocComment<'_>> {
+    let mut comments = vec![];
+    let mut lines: Vec<DocLine> = vec![];
+    // Prefix of the current line comment block, or `*/` in block comments
+    let mut kind: Option<&str> = None;
+
+    for (idx, text) in text.lines().enumerate() {
+        let trimmed = text.trim_start();
+        let indent = text.len() - trimmed.len();
+
+        if kind == Some("*/") {
+            let content_end = text.find("*/").unwrap_or(text.len());
+            let mut start = indent;
+            // Leading asterisks of block comment lines are no content
+            if trimmed.starts_with('*') && !trimmed.starts_with("*/") {
+                start += 1;
+            }
+            lines.push(DocLine {
+                line: idx + 1,
+                text: &text[..content_end],
+                start: start.min(content_end),
+            });
+            if content_end < text.len() {
+                comments.push(DocComment::new(std::mem::take(&mut lines)));
+                kind = None;
+            }
+            continue;
+        }
+
+        let line_kind = ["///", "//!"]
+            .iter()
+            .copied()
+            .find(|prefix| trimmed.starts_with(prefix) && !trimmed.starts_with("////"));
+        if kind.is_some() && line_kind != kind {
+            comments.push(DocComment::new(std::mem::take(&mut lines)));
+            kind = None;
+        }
+        if let Some(prefix) = line_kind {
+            kind = Some(prefix);
+            lines.push(DocLine {
+                line: idx + 1,
+                text,
+                start: indent + prefix.len(),
+            });
+            continue;
+        }
+
+        let is_block_start = (trimmed.starts_with("/**") && !trimmed.starts_with("/***"))
+            || trimmed.starts_with("/*!");
+        if is_block_start && !trimmed.starts_with("/**/") {
+            let start = indent + 3;
+            match text[start..].find("*/") {
+                Some(end) => comments.push(DocComment::new(vec![DocLine {
+                    line: idx + 1,
+                    text: &text[..start + end],
+                    start,
+                }])),
+                None => {
+                    kind = Some("*/");
+                    lines.push(DocLine {
+                        line: idx + 1,
+                        text,
+                        start,
+                    });
+                }
+            }
+        }
+    }
+    if !lines.is_empty() {
+        comments.push(DocComment::new(lines));
+    }
+    comments
+}
+
+/// Intra-doc links point to Rust items like `` `Vec` ``, `crate::Config` or
+/// `fn@run()` instead of URLs or files. A bare word like `guide` is a relative
+/// file link, so the target needs backticks, a path, a disambiguator or a
+/// `()` or `!` suffix.
+fn is_intra_doc_link(target: &str) -> bool {
+    lazy_static! {
+        static ref DISAMBIGUATOR: Regex = Regex::new(r"^[[:alpha:]]+@").unwrap();
+    }
+    let target = target.trim();
+    let item = target.trim_matches('`');
+    let has_marker = target.starts_with('`')
+        || item.contains("::")
+        || DISAMBIGUATOR.is_match(item)
+        || item.ends_with("()")
+        || item.ends_with('!');
+    has_marker && is_item_path(item)
+}
+
+/// Path of a Rust item like `Vec`, `crate::Config`, `Option<T>` or `fn@run()`
+fn is_item_path(target: &str) -> bool {
+    lazy_static! {
+        static ref ITEM_PATH: Regex = Regex::new(
+            r"^(?:(?:struct|enum|trait|union|mod|module|const|constant|fn|function|method|derive|type|value|macro|prim|primitive|field|variant|tymethod|static)@)?(?:(?:crate|self|super|[[:alpha:]_][[:word:]]*)(?:::[[:alpha:]_][[:word:]]*)*)(?:<[^>]*>)?(?:\(\)|!)?$"
+        )
+        .unwrap();
+    }
+    ITEM_PATH.is_match(target.trim().trim_matches('`'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn link(target: &str, line: usize, column: usize) -> Result<MarkupLink, BrokenExtractedLink> {
+        Ok(MarkupLink {
+            source: "".to_string(),
+            target: target.to_string(),
+            line,
+            column,
+            location: None,
+            base: None,
+            from_code: false,
+            wiki_link: false,
+        })
+    }
+
+    #[test]
+    fn no_link() {
+        let le = RustDocLinkExtractor::default();
+        let input = "// [Normal comment](https://example.com)\nfn main() {\n    let s = \"/// [string](https://example.com)\";\n}";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn line_comments() {
+        let le = RustDocLinkExtractor::default();
+        let input = "//! Crate docs with [link](https://crate.com).\n\n/// Function docs.\n///\n/// See the [guide](./docs/guide.md) and\n///   [über](https://example.com/ü).\nfn run() {}";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://crate.com", 1, 21),
+                link("./docs/guide.md", 5, 13),
+                link("https://example.com/ü", 6, 7),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn block_comments() {
+        let le = RustDocLinkExtractor::default();
+        let input = "/** Single [line](https://single.com) */\nstruct A;\n\n    /**\n     * Multi line\n     * [link](https://multi.com)\n     */\n    fn a() {}\n/*!\n[inner](https://inner.com)\n*/";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("https://single.com", 1, 12),
+                link("https://multi.com", 6, 8),
+                link("https://inner.com", 10, 1),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn code_blocks_in_docs() {
+        let le = RustDocLinkExtractor::default();
+        let input = "/// ```\n/// let x = \"[not](https://code.com)\";\n/// ```\n///\n///     [indented](https://indented.com)\nfn a() {}";
+        let result = le.find_links(input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn relative_link_without_extension() {
+        let le = RustDocLinkExtractor::default();
+        let input = "/// See the [changelog](CHANGELOG) and the [guide](guide).\nfn a() {}";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("CHANGELOG", 1, 13), link("guide", 1, 44)], result);
+    }
+
+    #[test]
+    fn markdown_options() {
+        let le = RustDocLinkExtractor {
+            markdown: MarkdownLinkExtractor {
+                autolink_literals: true,
+                ..MarkdownLinkExtractor::default()
+            },
+        };
+        let input = "/// See https://bare.com\nfn a() {}";
+        assert_eq!(vec![link("https://bare.com", 1, 9)], le.find_links(input));
+        assert!(RustDocLinkExtractor::default().find_links(input).is_empty());
+    }
+
+    #[test]
+    fn multi_byte_prefix() {
+        let le = RustDocLinkExtractor::default();
+        let input = "    /// Über [x](https://a.com)\nfn a() {}";
+        assert_eq!(vec![link("https://a.com", 1, 14)], le.find_links(input));
+    }
+
+    #[test]
+    fn intra_doc_links() {
+        let le = RustDocLinkExtractor::default();
+        let input = "/// Uses [`Vec`], [Config], [run](crate::run), [`fn@run()`] and [vec!].\n/// Also [custom][map] and [broken][not a label].\n///\n/// [map]: std::collections::HashMap\nfn a() {}";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![Err(BrokenExtractedLink {
+                source: "".to_string(),
+                reference: "not a label".to_string(),
+                line: 2,
+                column: 28,
+                error: "Markdown reference not found".to_string(),
+            })],
+            result
+        );
+    }
+
+    #[test_case("`Vec`")]
+    #[test_case("std::vec::Vec")]
+    #[test_case("crate::Config")]
+    #[test_case("Vec::new()")]
+    #[test_case("run()")]
+    #[test_case("vec!")]
+    #[test_case("struct@Config")]
+    #[test_case("`Option<T>`")]
+    fn intra_doc_link(target: &str) {
+        assert!(is_intra_doc_link(target));
+    }
+
+    #[test_case("https://docs.rs")]
+    #[test_case("./guide.md")]
+    #[test_case("../README.md#usage")]
+    #[test_case("not a path")]
+    #[test_case("CHANGELOG")]
+    #[test_case("guide")]
+    fn no_intra_doc_link(target: &str) {
+        assert!(!is_intra_doc_link(target));
+    }
+}
@@ -0,0 +1,193 @@
+/// Module for generating heading anchors (slugs) the way markup renderers do
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Algorithm used by a markup renderer to create the anchors of headings
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugStyle {
+    #[default]
+    GitHub,
+    GitLab,
+    MdBook,
+    Docusaurus,
+    Pandoc,
+}
+
+impl FromStr for SlugStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<SlugStyle, ()> {
+        match s {
+            "github" => Ok(SlugStyle::GitHub),
+            "gitlab" => Ok(SlugStyle::GitLab),
+            "mdbook" => Ok(SlugStyle::MdBook),
+            "docusaurus" => Ok(SlugStyle::Docusaurus),
+            "pandoc" => Ok(SlugStyle::Pandoc),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Generates unique slugs for the headings of one document.
+///
+/// Headings with the same text get a numeric suffix (`-1`, `-2`, ...) to keep
+/// the resulting anchors unique. All supported renderers do it this way.
+#[derive(Debug, Default)]
+pub struct Slugger {
+    style: SlugStyle,
+    occurrences: HashMap<String, usize>,
+}
+
+impl Slugger {
+    pub fn new(style: SlugStyle) -> Self {
+        Self {
+            style,
+            occurrences: HashMap::new(),
+        }
+    }
+
+    /// Create the unique slug for the given heading text
+    pub fn slug(&mut self, heading: &str) -> String {
+        let original = match self.style {
+            // Docusaurus uses the same slugger library as GitHub
+            SlugStyle::GitHub | SlugStyle::Docusaurus => github_slug(heading),
+            SlugStyle::GitLab => gitlab_slug(heading),
+            SlugStyle::MdBook => mdbook_slug(heading),
+            SlugStyle::Pandoc => pandoc_slug(heading),
+        };
+        let mut slug = original.clone();
+        while self.occurrences.contains_key(&slug) {
+            let count = self.occurrences.entry(original.clone()).or_default();
+            *count += 1;
+            slug = format!("{original}-{count}");
+        }
+        self.occurrences.insert(slug.clone(), 0);
+        slug
+    }
+
+    /// Register an explicitly set anchor such as `{#custom-id}`
+    pub fn custom(&mut self, id: &str) -> String {
+        self.occurrences.insert(id.to_string(), 0);
+        id.to_string()
+    }
+}
+
+/// Slug of a heading as generated by GitHub: lower case, spaces replaced by
+/// hyphens and all punctuation except `-` and `_` removed.
+fn github_slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Slug of a heading as generated by GitLab: like GitHub, but consecutive
+/// hyphens are merged into one.
+fn gitlab_slug(heading: &str) -> String {
+    let mut slug = String::new();
+    for c in github_slug(heading).chars() {
+        if c != '-' || !slug.ends_with('-') {
+            slug.push(c);
+        }
+    }
+    slug
+}
+
+/// Slug of a heading as generated by mdBook: only ASCII characters are
+/// converted to lower case and all whitespace is replaced by hyphens.
+fn mdbook_slug(heading: &str) -> String {
+    heading
+        .trim()
+        .chars()
+        .filter_map(|c| match c {
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c.to_ascii_lowercase()),
+            c if c.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Slug of a heading as generated by Pandoc: periods are kept, words are
+/// joined by single hyphens and everything before the first letter is
+/// removed. Empty slugs become `section`.
+fn pandoc_slug(heading: &str) -> String {
+    let filtered: String = heading
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    let slug: String = filtered
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .skip_while(|c| !c.is_alphabetic())
+        .collect();
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("Chapter 1", "chapter-1")]
+    #[test_case("Installation & Setup", "installation--setup")]
+    #[test_case("What's new?", "whats-new")]
+    #[test_case("snake_case-and-kebab", "snake_case-and-kebab")]
+    #[test_case("Überschrift mit Umlaut", "überschrift-mit-umlaut")]
+    #[test_case("  trimmed  ", "trimmed")]
+    fn slugs(heading: &str, expected: &str) {
+        assert_eq!(Slugger::default().slug(heading), expected);
+    }
+
+    #[test]
+    fn duplicate_headings() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Usage"), "usage");
+        assert_eq!(slugger.slug("Usage"), "usage-1");
+        assert_eq!(slugger.slug("Usage"), "usage-2");
+        assert_eq!(slugger.slug("Usage 1"), "usage-1-1");
+    }
+
+    #[test]
+    fn custom_id_is_unique() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.custom("usage"), "usage");
+        assert_eq!(slugger.slug("Usage"), "usage-1");
+    }
+
+    #[test]
+    fn slug_styles() {
+        let heading = "1. Über the API & v2.0 🚀";
+        let expected = [
+            (SlugStyle::GitHub, "1-über-the-api--v20-"),
+            (SlugStyle::Docusaurus, "1-über-the-api--v20-"),
+            (SlugStyle::GitLab, "1-über-the-api-v20-"),
+            (SlugStyle::MdBook, "1-Über-the-api--v20-"),
+            (SlugStyle::Pandoc, "über-the-api-v2.0"),
+        ];
+        for (style, slug) in expected {
+            assert_eq!(Slugger::new(style).slug(heading), slug, "{style:?}");
+        }
+    }
+
+    #[test]
+    fn pandoc_empty_slug() {
+        assert_eq!(Slugger::new(SlugStyle::Pandoc).slug("1.2 ?"), "section");
+    }
+}
@@ -0,0 +1,413 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+
+use super::ignore_comments::IgnoreRegions;
+use super::link_extractor::BrokenExtractedLink;
+use super::markdown_link_extractor::{code_urls, trim_autolink};
+
+/// Comment syntax of a programming language
+pub struct CommentSyntax {
+    /// File extensions of the language
+    extensions: &'static [&'static str],
+    /// Start of comments which end with the line
+    line: &'static [&'static str],
+    /// Start and end of block comments
+    block: &'static [(&'static str, &'static str)],
+    /// Quotes of string literals, which may contain comment markers
+    quotes: &'static [char],
+    /// Delimiters of string literals spanning multiple lines, like the triple
+    /// quotes of Python docstrings
+    long_quotes: &'static [&'static str],
+}
+
+const C_STYLE: [(&str, &str); 1] = [("/*", "*/")];
+const QUOTES: [char; 2] = ['"', '\''];
+const JS_QUOTES: [char; 3] = ['"', '\'', '`'];
+
+/// Comment syntax of the supported languages. Rust source files are checked
+/// with the `rs` markup type, which parses doc comments as markdown.
+const COMMENT_SYNTAXES: [CommentSyntax; 13] = [
+    CommentSyntax {
+        extensions: &[
+            "c", "h", "cc", "cpp", "cxx", "hh", "hpp", "hxx", "cs", "java", "kt", "kts", "scala",
+            "swift", "dart", "scss", "less",
+        ],
+        line: &["//"],
+        block: &C_STYLE,
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx", "go"],
+        line: &["//"],
+        block: &C_STYLE,
+        quotes: &JS_QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["php", "tf"],
+        line: &["//", "#"],
+        block: &C_STYLE,
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["css"],
+        line: &[],
+        block: &C_STYLE,
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["py", "pyi"],
+        line: &["#"],
+        block: &[],
+        quotes: &QUOTES,
+        long_quotes: &["\"\"\"", "'''"],
+    },
+    CommentSyntax {
+        extensions: &[
+            "rb", "pl", "pm", "sh", "bash", "zsh", "fish", "r", "ex", "exs", "cmake",
+        ],
+        line: &["#"],
+        block: &[],
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["ps1", "psm1"],
+        line: &["#"],
+        block: &[("<#", "#>")],
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["jl"],
+        line: &["#"],
+        block: &[("#=", "=#")],
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["sql"],
+        line: &["--"],
+        block: &C_STYLE,
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["lua"],
+        line: &["--"],
+        block: &[("--[[", "]]")],
+        quotes: &QUOTES,
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["hs", "elm"],
+        line: &["--"],
+        block: &[("{-", "-}")],
+        quotes: &['"'],
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["erl", "hrl"],
+        line: &["%"],
+        block: &[],
+        quotes: &['"'],
+        long_quotes: &[],
+    },
+    CommentSyntax {
+        extensions: &["vim"],
+        line: &["\""],
+        block: &[],
+        quotes: &['\''],
+        long_quotes: &[],
+    },
+];
+
+impl CommentSyntax {
+    fn from_path(path: &Path) -> Option<&'static CommentSyntax> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        COMMENT_SYNTAXES
+            .iter()
+            .find(|syntax| syntax.extensions.contains(&ext.as_str()))
+    }
+
+    /// File extensions of all supported languages
+    #[must_use]
+    pub fn file_extensions() -> Vec<String> {
+        COMMENT_SYNTAXES
+            .iter()
+            .flat_map(|syntax| syntax.extensions.iter())
+            .map(|ext| ext.to_string())
+            .collect()
+    }
+
+    /// Byte ranges of the content of all comments
+    fn comments(&self, text: &str) -> Vec<Range<usize>> {
+        let mut comments = vec![];
+        let mut idx = 0;
+        while let Some(c) = text[idx..].chars().next() {
+            let rest = &text[idx..];
+            if let Some((open, close)) = self.block.iter().find(|(open, _)| rest.starts_with(open))
+            {
+                let start = idx + open.len();
+                let end = text[start..]
+                    .find(close)
+                    .map_or(text.len(), |end| start + end);
+                comments.push(start..end);
+                idx = (end + close.len()).min(text.len());
+            } else if let Some(marker) = self
+                .line
+                .iter()
+                .find(|marker| rest.starts_with(*marker) && self.is_line_comment(text, idx, marker))
+            {
+                let start = idx + marker.len();
+                let end = text[start..]
+                    .find('\n')
+                    .map_or(text.len(), |end| start + end);
+                comments.push(start..end);
+                idx = end;
+            } else if let Some(quote) = self
+                .long_quotes
+                .iter()
+                .find(|quote| rest.starts_with(*quote))
+            {
+                idx = long_string_end(text, idx, quote);
+            } else if self.quotes.contains(&c) {
+                idx = string_end(text, idx, c);
+            } else {
+                idx += c.len_utf8();
+            }
+        }
+        comments
+    }
+
+    /// A `#` only starts a comment at the beginning of a word, so that shell
+    /// expressions like `${#array[@]}` or `$#` are no comments
+    fn is_line_comment(&self, text: &str, idx: usize, marker: &str) -> bool {
+        marker != "#"
+            || text[..idx]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace)
+    }
+}
+
+/// Offset after the string literal starting with the quote at `start`. Only
+/// backtick strings span multiple lines.
+fn string_end(text: &str, start: usize, quote: char) -> usize {
+    let mut chars = text[start + 1..].char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '\n' if quote != '`' => return start + 1 + idx,
+            c if c == quote => return start + 1 + idx + 1,
+            _ => (),
+        }
+    }
+    text.len()
+}
+
+/// Offset after the string literal starting with the delimiter `quote` at
+/// `start`, which may span multiple lines
+fn long_string_end(text: &str, start: usize, quote: &str) -> usize {
+    let mut idx = start + quote.len();
+    while let Some(c) = text[idx..].chars().next() {
+        if c == '\\' {
+            idx += 1;
+            idx += text[idx..].chars().next().map_or(0, char::len_utf8);
+        } else if text[idx..].starts_with(quote) {
+            return idx + quote.len();
+        } else {
+            idx += c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+/// Link extractor for comments of source code files.
+///
+/// Comments are found with the comment syntax of the language of the file
+/// extension. Web links and relative file paths starting with `./` or `../`
+/// are extracted. Ignore directives like `// mlc-disable-next-line` are
+/// written in the comment syntax of the language.
+pub struct SourceCodeLinkExtractor {
+    syntax: Option<&'static CommentSyntax>,
+}
+
+impl SourceCodeLinkExtractor {
+    #[must_use]
+    pub fn new(path: &str) -> Self {
+        SourceCodeLinkExtractor {
+            syntax: CommentSyntax::from_path(Path::new(path)),
+        }
+    }
+}
+
+impl LinkExtractor for SourceCodeLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        lazy_static! {
+            static ref RELATIVE_PATH: Regex =
+                Regex::new(r#"(?:^|[\s(\[<"'`])(\.\.?/[^\s"'<>`()\[\]]+)"#).unwrap();
+            static ref DIRECTIVE: Regex =
+                Regex::new(r"\bmlc-(?:disable-next-line|disable-line|disable|enable)\b").unwrap();
+        }
+        let syntax = match self.syntax {
+            Some(syntax) => syntax,
+            None => return vec![],
+        };
+        let comments = syntax.comments(text);
+
+        // Directives as html comments in the line of the source comment
+        let mut directives: Vec<String> = vec![String::new(); text.lines().count()];
+        for comment in &comments {
+            for directive in DIRECTIVE.find_iter(&text[comment.clone()]) {
+                let line = text[..comment.start + directive.start()]
+                    .matches('\n')
+                    .count();
+                if let Some(line) = directives.get_mut(line) {
+                    line.push_str(&format!("<!-- {} -->", directive.as_str()));
+                }
+            }
+        }
+        let ignore_regions = IgnoreRegions::from_text(&directives.join("\n"));
+
+        let mut links = vec![];
+        for comment in comments {
+            let content = &text[comment.clone()];
+            let mut targets = code_urls(content);
+            targets.extend(RELATIVE_PATH.captures_iter(content).map(|captures| {
+                let path = captures.get(1).unwrap();
+                (path.start(), trim_autolink(path.as_str()).to_string())
+            }));
+            targets.sort_by_key(|(idx, _)| *idx);
+            for (idx, target) in targets {
+                let offset = comment.start + idx;
+                let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+                let line = text[..line_start].matches('\n').count() + 1;
+                if ignore_regions.is_line_ignored(line) {
+                    continue;
+                }
+                links.push(Ok(MarkupLink {
+                    source: String::new(),
+                    target,
+                    line,
+                    column: text[line_start..offset].chars().count() + 1,
+                    location: None,
+                    base: None,
+                    from_code: false,
+                    wiki_link: false,
+                }));
+            }
+        }
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn targets(path: &str, input: &str) -> Vec<(String, usize, usize)> {
+        SourceCodeLinkExtractor::new(path)
+            .find_links(input)
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                (link.target, link.line, link.column)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn c_style_comments() {
+        let input = "// See https://example.com/spec.\nlet url = \"http://not-a-comment.com\"; // ../docs/design.md\n/* Block\n * (https://example.org/issue/1)\n */\nconst s = '//no/comment';";
+        assert_eq!(
+            vec![
+                ("https://example.com/spec".to_string(), 1, 8),
+                ("../docs/design.md".to_string(), 2, 42),
+                ("https://example.org/issue/1".to_string(), 4, 5),
+            ],
+            targets("main.ts", input)
+        );
+    }
+
+    #[test]
+    fn hash_comments() {
+        let input = "#!/bin/bash\n# Docs: ./README.md, https://example.com\necho \"# https://in-string.com\" ${#array[@]} # https://example.org\n";
+        assert_eq!(
+            vec![
+                ("./README.md".to_string(), 2, 9),
+                ("https://example.com".to_string(), 2, 22),
+                ("https://example.org".to_string(), 3, 47),
+            ],
+            targets("script.sh", input)
+        );
+    }
+
+    #[test]
+    fn docstrings() {
+        let input = "def f():\n    \"\"\"Don't use # https://in-docstring.com\n    # https://also-in-docstring.com\n    \"\"\"\n    return '''#x'''  # https://example.com\n";
+        assert_eq!(
+            vec![("https://example.com".to_string(), 5, 24)],
+            targets("main.py", input)
+        );
+    }
+
+    #[test]
+    fn template_literals() {
+        let input = "const a = `multi\n// https://in-template.com\n`; // https://example.com";
+        assert_eq!(
+            vec![("https://example.com".to_string(), 3, 7)],
+            targets("a.js", input)
+        );
+    }
+
+    #[test]
+    fn other_syntaxes() {
+        assert_eq!(
+            vec![("https://example.com".to_string(), 1, 4)],
+            targets("query.sql", "-- https://example.com\nSELECT '--x';")
+        );
+        assert_eq!(
+            vec![("https://example.com".to_string(), 2, 1)],
+            targets("init.lua", "--[[\nhttps://example.com\n]]")
+        );
+        assert_eq!(
+            vec![("https://example.com".to_string(), 1, 4)],
+            targets("Main.hs", "{- https://example.com -}")
+        );
+    }
+
+    #[test]
+    fn ignore_directives() {
+        let input = "# mlc-disable-next-line\n# https://ignored.com\n# https://ignored.org mlc-disable-line\n# mlc-disable\n# ./ignored.md\n# mlc-enable\n# https://example.com";
+        assert_eq!(
+            vec![("https://example.com".to_string(), 7, 3)],
+            targets("main.py", input)
+        );
+    }
+
+    #[test_case("file.txt", "// https://example.com")]
+    #[test_case("main.go", "fmt.Println(\"https://example.com\")")]
+    #[test_case("main.py", "# www.example.com or a/b/c")]
+    #[test_case("main.c", "// https://${HOST}/path")]
+    fn no_link(path: &str, input: &str) {
+        let result = targets(path, input);
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test]
+    fn file_extensions() {
+        let extensions = CommentSyntax::file_extensions();
+        assert!(extensions.contains(&"py".to_string()));
+        assert!(!extensions.contains(&"rs".to_string()));
+    }
+}
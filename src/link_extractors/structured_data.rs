@@ -0,0 +1,568 @@
+use super::json::{Json, Parser, MAX_DEPTH};
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+/// Scalar value of a structured document like YAML or TOML
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DataValue {
+    /// Keys and array indices which lead to the value
+    pub path: Vec<String>,
+    /// The unquoted value
+    pub value: String,
+    /// Byte offset of the value in the document
+    pub offset: usize,
+}
+
+/// All string values of a TOML document
+pub fn toml_values(text: &str) -> Vec<DataValue> {
+    let mut values = vec![];
+    match DeTable::parse(text) {
+        Ok(table) => collect_toml_table(text, table.get_ref(), &mut vec![], &mut values),
+        Err(e) => warn!("Could not parse TOML. {e}"),
+    }
+    values.sort_by_key(|value| value.offset);
+    values
+}
+
+fn collect_toml_table(
+    text: &str,
+    table: &DeTable<'_>,
+    path: &mut Vec<String>,
+    values: &mut Vec<DataValue>,
+) {
+    for (key, value) in table {
+        path.push(key.get_ref().to_string());
+        collect_toml_value(text, value, path, values);
+        path.pop();
+    }
+}
+
+fn collect_toml_value(
+    text: &str,
+    value: &Spanned<DeValue<'_>>,
+    path: &mut Vec<String>,
+    values: &mut Vec<DataValue>,
+) {
+    match value.get_ref() {
+        DeValue::String(s) => {
+            let start = value.span().start;
+            let quotes = text[start..]
+                .chars()
+                .take(3)
+                .take_while(|c| *c == '"' || *c == '\'')
+                .count();
+            // Quotes of an empty string are no opening triple quote
+            let quotes = if quotes == 2 { 1 } else { quotes };
+            values.push(DataValue {
+                path: path.clone(),
+                value: s.to_string(),
+                offset: start + quotes,
+            });
+        }
+        DeValue::Array(array) => {
+            for (idx, item) in array.iter().enumerate() {
+                path.push(idx.to_string());
+                collect_toml_value(text, item, path, values);
+                path.pop();
+            }
+        }
+        DeValue::Table(table) => collect_toml_table(text, table, path, values),
+        _ => (),
+    }
+}
+
+/// All string values of a JSON document
+pub fn json_values(text: &str) -> Vec<DataValue> {
+    let mut values = vec![];
+    match Parser::new(text).parse() {
+        Ok(json) => {
+            let line_starts: Vec<usize> = std::iter::once(0)
+                .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+                .collect();
+            collect_json(text, &line_starts, &json, &mut vec![], &mut values);
+        }
+        Err(e) => warn!("Could not parse JSON. {e}"),
+    }
+    values
+}
+
+fn collect_json(
+    text: &str,
+    line_starts: &[usize],
+    json: &Json,
+    path: &mut Vec<String>,
+    values: &mut Vec<DataValue>,
+) {
+    match json {
+        Json::Object(members) => {
+            for (key, value) in members {
+                path.push(key.clone());
+                collect_json(text, line_starts, value, path, values);
+                path.pop();
+            }
+        }
+        Json::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                path.push(idx.to_string());
+                collect_json(text, line_starts, item, path, values);
+                path.pop();
+            }
+        }
+        Json::String(s) => {
+            // Positions of the parser are lines and character columns
+            if let Some((line, column)) = s.positions.first() {
+                let line_start = line_starts[line - 1];
+                let offset = line_start
+                    + text[line_start..]
+                        .chars()
+                        .take(column - 1)
+                        .map(char::len_utf8)
+                        .sum::<usize>();
+                values.push(DataValue {
+                    path: path.clone(),
+                    value: s.value.clone(),
+                    offset,
+                });
+            }
+        }
+        Json::Other => (),
+    }
+}
+
+/// Enclosing mapping key or sequence item of a YAML line
+struct Parent {
+    /// Column of the key or of the `-` of the item
+    indent: usize,
+    /// Index of a sequence item
+    index: Option<usize>,
+    segment: String,
+}
+
+/// All scalar values of a YAML document.
+///
+/// This is no complete YAML parser. Block mappings and sequences, flow
+/// collections on a single line as well as plain and quoted scalars are
+/// supported. Block scalars (`|` and `>`), multi line flow collections and
+/// aliases are skipped.
+pub fn yaml_values(text: &str) -> Vec<DataValue> {
+    let mut values = vec![];
+    let mut parents: Vec<Parent> = vec![];
+    // Lines indented deeper than the key of a block scalar belong to it
+    let mut block_scalar_indent: Option<usize> = None;
+    let mut line_start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let offset = line_start;
+        line_start += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        let trimmed = content.trim_start_matches(' ');
+        let mut column = content.len() - trimmed.len();
+
+        if let Some(indent) = block_scalar_indent {
+            if trimmed.trim().is_empty() || column > indent {
+                continue;
+            }
+            block_scalar_indent = None;
+        }
+        if content == "---" || content.starts_with("--- ") || content == "..." {
+            parents.clear();
+            continue;
+        }
+        if trimmed.trim().is_empty() || trimmed.starts_with('#') || trimmed.starts_with('%') {
+            continue;
+        }
+
+        let mut rest = trimmed;
+        let mut is_item = false;
+        while rest == "-" || rest.starts_with("- ") {
+            let mut index = 0;
+            while let Some(parent) = parents.last() {
+                if parent.indent > column || (parent.indent == column && parent.index.is_some()) {
+                    if let (true, Some(previous)) = (parent.indent == column, parent.index) {
+                        index = previous + 1;
+                    }
+                    parents.pop();
+                } else {
+                    break;
+                }
+            }
+            parents.push(Parent {
+                indent: column,
+                index: Some(index),
+                segment: index.to_string(),
+            });
+            let after = &rest[1..];
+            let item_content = after.trim_start_matches(' ');
+            column += 1 + after.len() - item_content.len();
+            rest = item_content;
+            is_item = true;
+        }
+        if rest.is_empty() || rest.starts_with('#') {
+            continue;
+        }
+
+        match mapping_entry(rest) {
+            Some((key, value_start)) => {
+                while parents.last().is_some_and(|parent| parent.indent >= column) {
+                    parents.pop();
+                }
+                let value = &rest[value_start..];
+                if value.is_empty() || value.starts_with('#') {
+                    parents.push(Parent {
+                        indent: column,
+                        index: None,
+                        segment: key,
+                    });
+                } else if value.starts_with('|') || value.starts_with('>') {
+                    block_scalar_indent = Some(column);
+                } else {
+                    let mut path = parent_path(&parents);
+                    path.push(key);
+                    let value_offset = offset + column + value_start;
+                    yaml_node(value, value_offset, &mut path, &mut values);
+                }
+            }
+            None if is_item => {
+                let mut path = parent_path(&parents);
+                yaml_node(rest, offset + column, &mut path, &mut values);
+            }
+            // Continuation of a multi line scalar
+            None => (),
+        }
+    }
+    values
+}
+
+fn parent_path(parents: &[Parent]) -> Vec<String> {
+    parents
+        .iter()
+        .map(|parent| parent.segment.clone())
+        .collect()
+}
+
+/// Key and start of the value of a `key: value` line
+fn mapping_entry(line: &str) -> Option<(String, usize)> {
+    let (key, key_end) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let (key, len) = quoted_scalar(line, quote)?;
+            (key, len)
+        }
+        '[' | '{' | '&' | '*' | '!' | '|' | '>' => return None,
+        _ => {
+            let end = line.match_indices(':').map(|(idx, _)| idx).find(|idx| {
+                line[idx + 1..].is_empty() || line[idx + 1..].starts_with([' ', '\t'])
+            })?;
+            let key = &line[..end];
+            if key.contains(" #") {
+                return None;
+            }
+            (key.trim_end().to_string(), end)
+        }
+    };
+    let after_key = &line[key_end..];
+    let after_colon = after_key.trim_start().strip_prefix(':')?;
+    if !(after_colon.is_empty() || after_colon.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let value = after_colon.trim_start();
+    Some((key, line.len() - value.len()))
+}
+
+/// Scalar or flow collection of a YAML line
+fn yaml_node(text: &str, offset: usize, path: &mut Vec<String>, values: &mut Vec<DataValue>) {
+    let mut text = text;
+    let mut offset = offset;
+    // Skip anchors and tags
+    while text.starts_with('&') || text.starts_with('!') {
+        let end = text.find([' ', '\t']).unwrap_or(text.len());
+        let rest = text[end..].trim_start();
+        offset += text.len() - rest.len();
+        text = rest;
+    }
+    match text.chars().next() {
+        Some('[' | '{') => {
+            let mut cursor = FlowCursor {
+                text,
+                pos: 0,
+                depth: 0,
+            };
+            if let Err(e) = cursor.node(offset, path, values) {
+                warn!("Could not parse YAML. {e}");
+            }
+        }
+        Some('*' | '#') | None => (),
+        Some(quote @ ('"' | '\'')) => {
+            if let Some((value, _)) = quoted_scalar(text, quote) {
+                values.push(DataValue {
+                    path: path.clone(),
+                    value,
+                    offset: offset + 1,
+                });
+            }
+        }
+        Some(_) => {
+            let end = text.find(" #").unwrap_or(text.len());
+            let value = text[..end].trim_end();
+            values.push(DataValue {
+                path: path.clone(),
+                value: value.to_string(),
+                offset,
+            });
+        }
+    }
+}
+
+/// Unescaped content of a quoted scalar at the start of the text and the
+/// length including the quotes
+fn quoted_scalar(text: &str, quote: char) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, escaped)) => value.push(escaped),
+                None => return None,
+            },
+            '\'' if quote == '\'' && chars.peek().map(|(_, c)| *c) == Some('\'') => {
+                chars.next();
+                value.push('\'');
+            }
+            c if c == quote => return Some((value, idx + 1)),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Parser of YAML flow collections like `[a, "b"]` or `{ url: c }`
+struct FlowCursor<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Number of enclosing flow collections
+    depth: usize,
+}
+
+impl FlowCursor<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Parse a node at the current position. `offset` is the byte offset of
+    /// the flow collection in the document.
+    fn node(
+        &mut self,
+        offset: usize,
+        path: &mut Vec<String>,
+        values: &mut Vec<DataValue>,
+    ) -> Result<(), String> {
+        self.skip_whitespace();
+        if matches!(self.peek(), Some('[' | '{')) && self.depth >= MAX_DEPTH {
+            return Err(format!("Nesting deeper than {MAX_DEPTH} levels"));
+        }
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                self.depth += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(']') | None => break,
+                        Some(',') => self.pos += 1,
+                        _ => {
+                            path.push(index.to_string());
+                            self.node(offset, path, values)?;
+                            path.pop();
+                            index += 1;
+                        }
+                    }
+                }
+                self.pos += 1;
+                self.depth -= 1;
+            }
+            Some('{') => {
+                self.pos += 1;
+                self.depth += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some('}') | None => break,
+                        Some(',') => self.pos += 1,
+                        _ => {
+                            let key = self.scalar();
+                            self.skip_whitespace();
+                            if self.peek() == Some(':') {
+                                self.pos += 1;
+                                if let Some((key, _)) = key {
+                                    path.push(key);
+                                    self.node(offset, path, values)?;
+                                    path.pop();
+                                }
+                            }
+                        }
+                    }
+                }
+                self.pos += 1;
+                self.depth -= 1;
+            }
+            _ => {
+                let start = self.pos;
+                if let Some((value, value_start)) = self.scalar() {
+                    values.push(DataValue {
+                        path: path.clone(),
+                        value,
+                        offset: offset + start + value_start,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Scalar at the current position and the offset of its content
+    fn scalar(&mut self) -> Option<(String, usize)> {
+        let rest = &self.text[self.pos..];
+        match rest.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let (value, len) = quoted_scalar(rest, quote)?;
+                self.pos += len;
+                Some((value, 1))
+            }
+            _ => {
+                let end = rest
+                    .char_indices()
+                    .find(|(idx, c)| {
+                        matches!(c, ',' | ']' | '}')
+                            || (*c == ':'
+                                && rest[idx + 1..].chars().next().is_none_or(|next| {
+                                    next.is_whitespace() || ",]}".contains(next)
+                                }))
+                    })
+                    .map_or(rest.len(), |(idx, _)| idx);
+                self.pos += end;
+                let value = rest[..end].trim_end();
+                if value.is_empty() {
+                    None
+                } else {
+                    Some((value.to_string(), 0))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(path: &str, value: &str, offset: usize) -> DataValue {
+        DataValue {
+            path: path.split('.').map(|s| s.to_string()).collect(),
+            value: value.to_string(),
+            offset,
+        }
+    }
+
+    #[test]
+    fn yaml_mappings_and_sequences() {
+        let input = "title: Hello # comment\nimage: \"/img/a.png\"\nlinks:\n  - https://a.com\n  - 'it''s.md'\n  - name: Home\n    url: ./index.md\nredirect_from:\n- /old/\n- /older/\nnested:\n  deep:\n    key: value\n";
+        let values = yaml_values(input);
+        let expected = vec![
+            value("title", "Hello", 7),
+            value("image", "/img/a.png", 31),
+            value("links.0", "https://a.com", 54),
+            value("links.1", "it's.md", 73),
+            value("links.2.name", "Home", 93),
+            value("links.2.url", "./index.md", 107),
+            value("redirect_from.0", "/old/", 135),
+            value("redirect_from.1", "/older/", 143),
+            value("nested.deep.key", "value", 176),
+        ];
+        assert_eq!(expected, values);
+        for value in &values {
+            assert!(input[value.offset..].starts_with(&value.value.replace("'s", "''s")));
+        }
+    }
+
+    #[test]
+    fn yaml_flow_collections() {
+        let input = "tags: [a, \"b, c\"]\nlinks: { home: /, docs: 'docs.md' }\nlist: [[x]]\n";
+        let values = yaml_values(input);
+        let expected = vec![
+            value("tags.0", "a", 7),
+            value("tags.1", "b, c", 11),
+            value("links.home", "/", 33),
+            value("links.docs", "docs.md", 43),
+            value("list.0.0", "x", 62),
+        ];
+        assert_eq!(expected, values);
+    }
+
+    #[test]
+    fn yaml_skipped_content() {
+        let input = "description: |\n  url: https://in-block.com\n  more\nalias: *anchor\nempty:\nurl: &anchor https://anchored.com\n---\nsecond: doc\n";
+        let values = yaml_values(input);
+        let expected = vec![
+            value("url", "https://anchored.com", 85),
+            value("second", "doc", 118),
+        ];
+        assert_eq!(expected, values);
+    }
+
+    #[test]
+    fn yaml_deeply_nested() {
+        let input = format!(
+            "a: {}x{}\nb: [{}y{}]\n",
+            "[".repeat(100_000),
+            "]".repeat(100_000),
+            "{k: ".repeat(MAX_DEPTH - 1),
+            "}".repeat(MAX_DEPTH - 1)
+        );
+        let values = yaml_values(&input);
+        assert_eq!(1, values.len());
+        assert_eq!("y", values[0].value);
+    }
+
+    #[test]
+    fn toml() {
+        let input = "title = \"Hello\"\nimage = '/img/a.png'\nlinks = [\"https://a.com\", \"\"\"b.md\"\"\"]\n\n[params]\ncanonical = \"https://c.com\"\nweight = 1\n";
+        let values = toml_values(input);
+        let expected = vec![
+            value("title", "Hello", 9),
+            value("image", "/img/a.png", 25),
+            value("links.0", "https://a.com", 47),
+            value("links.1", "b.md", 66),
+            value("params.canonical", "https://c.com", 98),
+        ];
+        assert_eq!(expected, values);
+    }
+
+    #[test]
+    fn invalid_toml() {
+        assert!(toml_values("key = ").is_empty());
+    }
+
+    #[test]
+    fn json() {
+        let input = "{\n  \"bugs\": {\"url\": \"https://a.com\"},\n  \"files\": [\"Ü.md\", 1, null],\n  \"escaped\": \"\\/b\"\n}";
+        let values = json_values(input);
+        let expected = vec![
+            value("bugs.url", "https://a.com", 21),
+            value("files.0", "Ü.md", 51),
+            value("escaped", "/b", 83),
+        ];
+        assert_eq!(expected, values);
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(json_values("{\"key\": ").is_empty());
+    }
+}
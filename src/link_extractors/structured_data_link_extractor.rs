@@ -0,0 +1,276 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+use wildmatch::WildMatch;
+
+use super::link_extractor::BrokenExtractedLink;
+use super::structured_data::{json_values, toml_values, yaml_values};
+
+/// Key paths of structured data files whose values are links
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct StructuredDataRule {
+    /// Pattern of the file paths relative to the checked directory with `?`
+    /// and `*` wildcards, like `*package.json`
+    pub files: String,
+    /// Key paths like `bugs.url`. A `*` segment matches any key or array
+    /// index, a `**` segment any number of keys.
+    pub keys: Vec<String>,
+}
+
+impl FromStr for StructuredDataRule {
+    type Err = ();
+
+    /// Parse a rule of the form `FILES=KEY,KEY`
+    fn from_str(s: &str) -> Result<StructuredDataRule, ()> {
+        let (files, keys) = s.split_once('=').ok_or(())?;
+        let keys: Vec<String> = keys
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+        if files.trim().is_empty() || keys.is_empty() {
+            return Err(());
+        }
+        Ok(StructuredDataRule {
+            files: files.trim().to_string(),
+            keys,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Yaml,
+    Json,
+    Toml,
+}
+
+impl DataFormat {
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<DataFormat> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "yaml" | "yml" => Some(DataFormat::Yaml),
+            "json" => Some(DataFormat::Json),
+            "toml" => Some(DataFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Link extractor for YAML, JSON and TOML files.
+///
+/// The values at the key paths of all rules matching the file are links.
+/// Files without matching rule have no links.
+pub struct StructuredDataLinkExtractor {
+    pub format: Option<DataFormat>,
+    pub keys: Vec<String>,
+}
+
+impl StructuredDataLinkExtractor {
+    /// Extractor with the key paths of the rules which match the file
+    #[must_use]
+    pub fn new(path: &str, directory: &Path, rules: &[StructuredDataRule]) -> Self {
+        let relative = Path::new(path)
+            .strip_prefix(directory)
+            .map_or(path.to_string(), |p| p.to_string_lossy().to_string())
+            .replace('\\', "/");
+        let path = path.replace('\\', "/");
+        let keys = rules
+            .iter()
+            .filter(|rule| {
+                let files = WildMatch::new(&rule.files);
+                files.matches(&relative) || files.matches(&path)
+            })
+            .flat_map(|rule| rule.keys.iter().cloned())
+            .collect();
+        StructuredDataLinkExtractor {
+            format: DataFormat::from_path(Path::new(&path)),
+            keys,
+        }
+    }
+}
+
+impl LinkExtractor for StructuredDataLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<Result<MarkupLink, BrokenExtractedLink>> {
+        if self.keys.is_empty() {
+            return vec![];
+        }
+        let values = match self.format {
+            Some(DataFormat::Yaml) => yaml_values(text),
+            Some(DataFormat::Json) => json_values(text),
+            Some(DataFormat::Toml) => toml_values(text),
+            None => return vec![],
+        };
+        let patterns: Vec<Vec<&str>> = self
+            .keys
+            .iter()
+            .map(|key| key.split('.').collect())
+            .collect();
+        values
+            .into_iter()
+            .filter(|value| !value.value.trim().is_empty())
+            .filter(|value| {
+                patterns
+                    .iter()
+                    .any(|pattern| matches_key_path(pattern, &value.path))
+            })
+            .map(|value| {
+                let line_start = text[..value.offset].rfind('\n').map_or(0, |idx| idx + 1);
+                Ok(MarkupLink {
+                    source: String::new(),
+                    target: value.value.trim().to_string(),
+                    line: text[..line_start].matches('\n').count() + 1,
+                    column: text[line_start..value.offset].chars().count() + 1,
+                    location: Some(value.path.join(".")),
+                    base: None,
+                    from_code: false,
+                    wiki_link: false,
+                })
+            })
+            .collect()
+    }
+}
+
+fn matches_key_path(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| matches_key_path(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(key, path)| {
+            (*segment == "*" || segment == key) && matches_key_path(rest, path)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn extractor(format: DataFormat, keys: &[&str]) -> StructuredDataLinkExtractor {
+        StructuredDataLinkExtractor {
+            format: Some(format),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+        }
+    }
+
+    fn targets(
+        le: &StructuredDataLinkExtractor,
+        input: &str,
+    ) -> Vec<(String, usize, usize, Option<String>)> {
+        le.find_links(input)
+            .into_iter()
+            .map(|link| {
+                let link = link.unwrap();
+                (link.target, link.line, link.column, link.location)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn toml() {
+        let le = extractor(
+            DataFormat::Toml,
+            &["package.homepage", "package.repository"],
+        );
+        let input = "[package]\nname = \"mlc\"\nhomepage = \"https://example.com\"\nrepository = \"https://example.com/repo\"\n";
+        assert_eq!(
+            vec![
+                (
+                    "https://example.com".to_string(),
+                    3,
+                    13,
+                    Some("package.homepage".to_string())
+                ),
+                (
+                    "https://example.com/repo".to_string(),
+                    4,
+                    15,
+                    Some("package.repository".to_string())
+                ),
+            ],
+            targets(&le, input)
+        );
+    }
+
+    #[test]
+    fn json() {
+        let le = extractor(DataFormat::Json, &["bugs.url"]);
+        let input = "{\n  \"bugs\": {\n    \"url\": \"https://example.com/issues\"\n  },\n  \"url\": \"no\"\n}";
+        assert_eq!(
+            vec![(
+                "https://example.com/issues".to_string(),
+                3,
+                13,
+                Some("bugs.url".to_string())
+            )],
+            targets(&le, input)
+        );
+    }
+
+    #[test]
+    fn yaml() {
+        let le = extractor(
+            DataFormat::Yaml,
+            &["contact_links.*.url", "**.externalDocs.url"],
+        );
+        let input = "contact_links:\n  - name: Docs\n    url: https://example.com/docs\n  - url: \"\"\npaths:\n  /pets:\n    get:\n      externalDocs:\n        url: https://example.com/pets\n";
+        assert_eq!(
+            vec![
+                (
+                    "https://example.com/docs".to_string(),
+                    3,
+                    10,
+                    Some("contact_links.0.url".to_string())
+                ),
+                (
+                    "https://example.com/pets".to_string(),
+                    9,
+                    14,
+                    Some("paths./pets.get.externalDocs.url".to_string())
+                ),
+            ],
+            targets(&le, input)
+        );
+    }
+
+    #[test]
+    fn no_keys() {
+        let le = extractor(DataFormat::Toml, &[]);
+        let result = le.find_links("homepage = \"https://example.com\"");
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[test_case("a.b", "a.b", true)]
+    #[test_case("a.*", "a.b", true)]
+    #[test_case("a.*", "a.b.c", false)]
+    #[test_case("**.c", "a.b.c", true)]
+    #[test_case("**.c", "c", true)]
+    #[test_case("a.**", "a.b.c", true)]
+    #[test_case("a.b", "a", false)]
+    #[test_case("a", "a.b", false)]
+    fn key_path(pattern: &str, path: &str, matches: bool) {
+        let pattern: Vec<&str> = pattern.split('.').collect();
+        let path: Vec<String> = path.split('.').map(str::to_string).collect();
+        assert_eq!(matches, matches_key_path(&pattern, &path));
+    }
+
+    #[test]
+    fn rules() {
+        let rules = vec![
+            "*Cargo.toml=package.homepage".parse().unwrap(),
+            "*.json=bugs.url, homepage".parse().unwrap(),
+        ];
+        let le = StructuredDataLinkExtractor::new("./docs/Cargo.toml", Path::new("./docs"), &rules);
+        assert_eq!(Some(DataFormat::Toml), le.format);
+        assert_eq!(vec!["package.homepage".to_string()], le.keys);
+        let le = StructuredDataLinkExtractor::new("package.json", Path::new("."), &rules);
+        assert_eq!(
+            vec!["bugs.url".to_string(), "homepage".to_string()],
+            le.keys
+        );
+        assert_eq!(Err(()), "package.json".parse::<StructuredDataRule>());
+    }
+}
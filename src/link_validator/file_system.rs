@@ -1,4 +1,7 @@
+use crate::link_extractors::link_extractor::find_anchors;
+use crate::link_extractors::slug::SlugStyle;
 use crate::link_validator::LinkCheckResult;
+use crate::markup::{MarkupFile, MarkupType};
 use crate::Config;
 use async_std::fs::canonicalize;
 use async_std::path::Path;
@@ -7,21 +10,43 @@ use std::path::MAIN_SEPARATOR;
 use walkdir::WalkDir;
 
 pub async fn check_filesystem(target: &str, config: &Config) -> LinkCheckResult {
-    let target = Path::new(target);
+    let (target, fragment) = split_fragment(target);
+    match find_target_file(Path::new(target), config).await {
+        Ok(file) => match fragment {
+            Some(fragment) => {
+                let slug_style = config.optional.slug_style.unwrap_or_default();
+                check_fragment(&file, fragment, slug_style).await
+            }
+            None => LinkCheckResult::Ok,
+        },
+        Err(msg) => LinkCheckResult::Failed(msg),
+    }
+}
+
+/// Split a resolved target into the path and the fragment after the first
+/// `#`, like `resolve_target_link` does. The fragment may contain slashes.
+fn split_fragment(target: &str) -> (&str, Option<&str>) {
+    match target.find('#') {
+        Some(idx) => (&target[..idx], Some(&target[idx + 1..])),
+        None => (target, None),
+    }
+}
+
+async fn find_target_file(target: &Path, config: &Config) -> Result<PathBuf, String> {
     debug!("Absolute target path {target:?}");
     if target.exists().await {
-        LinkCheckResult::Ok
+        Ok(target.to_path_buf())
     } else if !config.optional.match_file_extension.unwrap_or_default()
         && target.extension().is_none()
     {
         // Check if file exists ignoring the file extension
         let target_file_name = match target.file_name() {
             Some(s) => s,
-            None => return LinkCheckResult::Failed("Target path not found.".to_string()),
+            None => return Err("Target path not found.".to_string()),
         };
         let target_parent = match target.parent() {
             Some(s) => s,
-            None => return LinkCheckResult::Failed("Target parent not found.".to_string()),
+            None => return Err("Target parent not found.".to_string()),
         };
         debug!("Check if file ignoring the extension exists.");
         if target_parent.exists().await {
@@ -33,46 +58,81 @@ pub async fn check_filesystem(target: &str, config: &Config) -> LinkCheckResult
                 .filter_map(Result::ok)
                 .filter(|e| !e.file_type().is_dir())
             {
-                let mut file_on_system = entry.into_path();
+                let file_path = entry.into_path();
+                let mut file_on_system = file_path.clone();
                 file_on_system.set_extension("");
                 match file_on_system.file_name() {
                     Some(file_name) => {
                         if target_file_name == file_name {
                             info!("Found file {file_on_system:?}");
-                            return LinkCheckResult::Ok;
+                            return Ok(file_path.into());
                         }
                     }
-                    None => {
-                        return LinkCheckResult::Failed("Target filename not found.".to_string())
-                    }
+                    None => return Err("Target filename not found.".to_string()),
                 }
             }
-            LinkCheckResult::Failed("Target not found.".to_string())
+            Err("Target not found.".to_string())
         } else {
-            LinkCheckResult::Failed("Target not found.".to_string())
+            Err("Target not found.".to_string())
         }
     } else {
-        LinkCheckResult::Failed("Target filename not found.".to_string())
+        Err("Target filename not found.".to_string())
     }
 }
 
-pub async fn resolve_target_link(source: &str, target: &str, config: &Config) -> String {
-    let mut normalized_link = target.replace(['/', '\\'], std::path::MAIN_SEPARATOR_STR);
-    if let Some(idx) = normalized_link.find('#') {
-        info!(
-            "Strip everything after #. The chapter part '{}' is not checked.",
-            &normalized_link[idx..]
-        );
-        normalized_link = normalized_link[..idx].to_string();
+/// Check that the fragment matches an anchor of the target file
+async fn check_fragment(file: &Path, fragment: &str, slug_style: SlugStyle) -> LinkCheckResult {
+    // Be lenient with links like `file.md###chapter`
+    let fragment = fragment.trim_start_matches('#');
+    if fragment.is_empty() || file.is_dir().await {
+        return LinkCheckResult::Ok;
     }
+    let markup_type = match MarkupType::from_path(file.as_ref()) {
+        Some(markup_type) => markup_type,
+        None => {
+            debug!("Unknown markup type of {file:?}. Skip check of fragment '{fragment}'.");
+            return LinkCheckResult::Ok;
+        }
+    };
+    let markup_file = MarkupFile {
+        markup_type,
+        path: file.to_string_lossy().to_string(),
+    };
+    match find_anchors(&markup_file, slug_style) {
+        Some(anchors) => {
+            let decoded = url_escape::decode(fragment);
+            if anchors.iter().any(|anchor| anchor == &decoded) {
+                LinkCheckResult::Ok
+            } else {
+                LinkCheckResult::Failed(format!("Anchor '#{fragment}' not found in target file."))
+            }
+        }
+        None => {
+            debug!("Anchors of {file:?} are unknown. Skip check of fragment '{fragment}'.");
+            LinkCheckResult::Ok
+        }
+    }
+}
+
+pub async fn resolve_target_link(source: &str, target: &str, config: &Config) -> String {
+    let (link, fragment) = match target.find('#') {
+        Some(idx) => (&target[..idx], Some(&target[idx + 1..])),
+        None => (target, None),
+    };
+    let normalized_link = link.replace(['/', '\\'], std::path::MAIN_SEPARATOR_STR);
     let mut fs_link_target = Path::new(&normalized_link).to_path_buf();
-    if normalized_link.starts_with(MAIN_SEPARATOR) && config.optional.root_dir.is_some() {
-        match canonicalize(&config.optional.root_dir.as_ref().unwrap()).await {
-            Ok(new_root) => fs_link_target = new_root.join(Path::new(&normalized_link[1..])),
-            Err(e) => panic!(
-                "Root path could not be converted to an absolute path. Does the directory exit? {}",
-                e
-            ),
+    if normalized_link.is_empty() {
+        // Link to a fragment within the source file itself
+        fs_link_target = canonicalize(source).await.expect("Expected path to exist.");
+    } else if normalized_link.starts_with(MAIN_SEPARATOR) {
+        if let Some(root_dir) = &config.optional.root_dir {
+            match canonicalize(root_dir).await {
+                Ok(new_root) => fs_link_target = new_root.join(Path::new(&normalized_link[1..])),
+                Err(e) => panic!(
+                    "Root path could not be converted to an absolute path. Does the directory exit? {}",
+                    e
+                ),
+            }
         }
     }
 
@@ -83,10 +143,11 @@ pub async fn resolve_target_link(source: &str, target: &str, config: &Config) ->
         .expect("Could not resolve target path")
         .to_string();
     // Remove verbatim path identifier which causes trouble on windows when using ../../ in paths
-    abs_path
-        .strip_prefix("\\\\?\\")
-        .unwrap_or(&abs_path)
-        .to_string()
+    let abs_path = abs_path.strip_prefix("\\\\?\\").unwrap_or(&abs_path);
+    match fragment {
+        Some(fragment) if !fragment.is_empty() => format!("{abs_path}#{fragment}"),
+        _ => abs_path.to_string(),
+    }
 }
 
 async fn absolute_target_path(source: &str, target: &PathBuf) -> PathBuf {
@@ -108,9 +169,8 @@ async fn absolute_target_path(source: &str, target: &PathBuf) -> PathBuf {
 mod test {
     use super::*;
 
-    #[tokio::test]
-    async fn remove_dot() {
-        let source = Path::new(file!())
+    fn benchmark_dir() -> PathBuf {
+        Path::new(file!())
             .parent()
             .unwrap()
             .parent()
@@ -118,7 +178,12 @@ mod test {
             .parent()
             .unwrap()
             .join("benches")
-            .join("benchmark");
+            .join("benchmark")
+    }
+
+    #[tokio::test]
+    async fn remove_dot() {
+        let source = benchmark_dir();
         let target = Path::new("./script_and_comments.md").to_path_buf();
 
         let path = absolute_target_path(source.to_str().unwrap(), &target).await;
@@ -127,4 +192,113 @@ mod test {
         println!("{path_str:?}");
         assert_eq!(path_str.matches('.').count(), 1);
     }
+
+    #[tokio::test]
+    async fn resolve_fragment_in_same_file() {
+        let source = benchmark_dir().join("markdown").join("HashLinks.md");
+        let source = source.to_str().unwrap();
+
+        let target = resolve_target_link(source, "#chapter-2", &Config::default()).await;
+
+        let expected = canonicalize(source).await.unwrap();
+        assert_eq!(target, format!("{}#chapter-2", expected.to_str().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn check_existing_heading_anchor() {
+        let target = benchmark_dir()
+            .join("markdown")
+            .join("HashLinks.md#chapter-21");
+
+        let result = check_filesystem(target.to_str().unwrap(), &Config::default()).await;
+
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn check_missing_heading_anchor() {
+        let target = benchmark_dir()
+            .join("markdown")
+            .join("HashLinks.md#chapter-4");
+
+        let result = check_filesystem(target.to_str().unwrap(), &Config::default()).await;
+
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("Anchor '#chapter-4' not found in target file.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_anchor_with_slash() {
+        let source = benchmark_dir().join("markdown").join("HashLinks.md");
+        let target = resolve_target_link(
+            source.to_str().unwrap(),
+            "./HashLinks.md#a/b",
+            &Config::default(),
+        )
+        .await;
+
+        let result = check_filesystem(&target, &Config::default()).await;
+
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("Anchor '#a/b' not found in target file.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_anchor_without_file_extension() {
+        let target = benchmark_dir()
+            .join("markdown")
+            .join("ref_links#ref-link-chapter");
+
+        let result = check_filesystem(target.to_str().unwrap(), &Config::default()).await;
+
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn check_existing_html_anchors() {
+        for anchor in ["fn.parse", "legacy-anchor"] {
+            let target = benchmark_dir()
+                .join("html")
+                .join(format!("anchors.html#{anchor}"));
+
+            let result = check_filesystem(target.to_str().unwrap(), &Config::default()).await;
+
+            assert_eq!(result, LinkCheckResult::Ok);
+        }
+    }
+
+    #[tokio::test]
+    async fn check_missing_html_anchor() {
+        let target = benchmark_dir().join("html").join("anchors.html#fn.render");
+
+        let result = check_filesystem(target.to_str().unwrap(), &Config::default()).await;
+
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("Anchor '#fn.render' not found in target file.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_asciidoc_anchors() {
+        let file = benchmark_dir().join("asciidoc").join("anchors.adoc");
+        for (anchor, expected) in [
+            ("getting-started", LinkCheckResult::Ok),
+            ("install-steps", LinkCheckResult::Ok),
+            (
+                "install",
+                LinkCheckResult::Failed("Anchor '#install' not found in target file.".to_string()),
+            ),
+        ] {
+            let target = format!("{}#{anchor}", file.to_str().unwrap());
+
+            let result = check_filesystem(&target, &Config::default()).await;
+
+            assert_eq!(result, expected);
+        }
+    }
 }
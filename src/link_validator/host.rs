@@ -0,0 +1,291 @@
+use crate::link_validator::retry::RetryPolicy;
+use crate::link_validator::status::{StatusMapping, StatusRange};
+use crate::link_validator::timeout::Timeouts;
+use crate::OptionalConfig;
+use serde::Deserialize;
+use url::Url;
+use wildmatch::WildMatch;
+
+/// Method of the first request of a web link
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpMethod {
+    /// A HEAD request, which is repeated as GET request if it fails
+    #[default]
+    Head,
+    /// Only a GET request, for servers which do not answer HEAD requests properly
+    Get,
+}
+
+/// Options of the web requests to hosts matching a pattern. Unset options
+/// are taken from the global options.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct HostConfig {
+    /// Pattern of the host name with `?` and `*` wildcards, like `*.example.com`
+    pub host: String,
+    /// Headers of the form `Name: Value`, which are sent in addition to the
+    /// global http headers
+    pub headers: Option<Vec<String>>,
+    /// Status codes which are accepted as valid response, like `999` or
+    /// `"400-403"`. Replaces the global list.
+    #[serde(rename(deserialize = "accept-status"))]
+    pub accept_status: Option<Vec<StatusRange>>,
+    /// Results of status codes, like `"401=ignore"`. They are checked before
+    /// the global ones.
+    #[serde(rename(deserialize = "status-map"))]
+    pub status_map: Option<Vec<StatusMapping>>,
+    pub method: Option<HttpMethod>,
+    #[serde(rename(deserialize = "connect-timeout"))]
+    pub connect_timeout: Option<u64>,
+    #[serde(rename(deserialize = "read-timeout"))]
+    pub read_timeout: Option<u64>,
+    pub timeout: Option<u64>,
+    /// Wait time in milliseconds in between requests to each matching host
+    pub throttle: Option<u32>,
+    /// Maximum number of requests to each matching host at the same time
+    #[serde(rename(deserialize = "max-concurrency"))]
+    pub max_concurrency: Option<usize>,
+    pub retries: Option<u32>,
+    #[serde(rename(deserialize = "retry-delay"))]
+    pub retry_delay: Option<u64>,
+    #[serde(rename(deserialize = "retry-max-wait"))]
+    pub retry_max_wait: Option<u64>,
+    /// Accept invalid TLS certificates, like self-signed or expired ones
+    #[serde(rename(deserialize = "accept-invalid-certs"))]
+    pub accept_invalid_certs: Option<bool>,
+    /// Accept TLS certificates which are not valid for the host name
+    #[serde(rename(deserialize = "accept-invalid-hostnames"))]
+    pub accept_invalid_hostnames: Option<bool>,
+}
+
+impl HostConfig {
+    /// The first host block whose pattern matches the host
+    #[must_use]
+    pub fn find<'a>(hosts: &'a [HostConfig], host: &str) -> Option<&'a HostConfig> {
+        hosts
+            .iter()
+            .find(|config| WildMatch::new(&config.host).matches(host))
+    }
+}
+
+/// TLS options of the connections to a host
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TlsOptions {
+    pub accept_invalid_certs: bool,
+    pub accept_invalid_hostnames: bool,
+}
+
+/// Options of the web requests of a link
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    pub headers: Vec<(String, String)>,
+    pub method: HttpMethod,
+    /// Status codes which are accepted in addition to success and redirection
+    pub accept_status: Vec<StatusRange>,
+    /// Results of status codes. The first matching entry is used.
+    pub status_map: Vec<StatusMapping>,
+    pub retry: RetryPolicy,
+    pub timeouts: Timeouts,
+    pub tls: TlsOptions,
+}
+
+impl RequestOptions {
+    /// Options of the requests of the URL. The options of the first host block
+    /// which matches the host of the URL take precedence over the global ones.
+    #[must_use]
+    pub fn new(config: &OptionalConfig, url: &str, http_headers: &[(String, String)]) -> Self {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string));
+        let host = host.and_then(|host| HostConfig::find(config.hosts.as_deref()?, &host));
+        let mut headers = http_headers.to_vec();
+        if let Some(host_headers) = host.and_then(|host| host.headers.as_ref()) {
+            headers.extend(parse_http_headers(host_headers));
+        }
+        RequestOptions {
+            headers,
+            method: host.and_then(|host| host.method).unwrap_or_default(),
+            accept_status: host
+                .and_then(|host| host.accept_status.clone())
+                .or_else(|| config.accept_status.clone())
+                .unwrap_or_default(),
+            status_map: host
+                .and_then(|host| host.status_map.as_ref())
+                .into_iter()
+                .chain(config.status_map.as_ref())
+                .flatten()
+                .copied()
+                .collect(),
+            retry: RetryPolicy::new(config, host),
+            timeouts: Timeouts::new(config, host),
+            tls: TlsOptions {
+                accept_invalid_certs: host
+                    .and_then(|host| host.accept_invalid_certs)
+                    .unwrap_or_default(),
+                accept_invalid_hostnames: host
+                    .and_then(|host| host.accept_invalid_hostnames)
+                    .unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// Parse http headers of the form `Name: Value`. Invalid headers are skipped.
+#[must_use]
+pub fn parse_http_headers(headers: &[String]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|h| {
+            let parts: Vec<&str> = h.splitn(2, ':').collect();
+            if parts.len() == 2 {
+                Some((parts[0].trim().to_string(), parts[1].trim().to_string()))
+            } else {
+                warn!("Invalid HTTP header format (expected 'Name: Value'): {}", h);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config() -> OptionalConfig {
+        OptionalConfig {
+            retries: Some(1),
+            accept_status: Some(vec![status("4xx")]),
+            status_map: Some(vec![mapping("401=ignore"), mapping("403=warn")]),
+            hosts: Some(vec![
+                HostConfig {
+                    host: "*.linkedin.com".to_string(),
+                    accept_status: Some(vec![status("999")]),
+                    method: Some(HttpMethod::Get),
+                    status_map: Some(vec![mapping("403=ok")]),
+                    ..HostConfig::default()
+                },
+                HostConfig {
+                    host: "api.github.com".to_string(),
+                    headers: Some(vec!["Authorization: Bearer token".to_string()]),
+                    retries: Some(3),
+                    timeout: Some(5000),
+                    accept_invalid_certs: Some(true),
+                    ..HostConfig::default()
+                },
+                HostConfig {
+                    host: "*.com".to_string(),
+                    accept_status: Some(vec![status("403")]),
+                    ..HostConfig::default()
+                },
+            ]),
+            ..OptionalConfig::default()
+        }
+    }
+
+    fn status(range: &str) -> StatusRange {
+        range.parse().unwrap()
+    }
+
+    fn mapping(mapping: &str) -> StatusMapping {
+        mapping.parse().unwrap()
+    }
+
+    fn global_headers() -> Vec<(String, String)> {
+        vec![("User-Agent".to_string(), "mlc".to_string())]
+    }
+
+    #[test]
+    fn no_matching_host() {
+        let options = RequestOptions::new(&config(), "https://example.org", &global_headers());
+        assert_eq!(global_headers(), options.headers);
+        assert_eq!(HttpMethod::Head, options.method);
+        assert_eq!(vec![status("4xx")], options.accept_status);
+        assert_eq!(
+            vec![mapping("401=ignore"), mapping("403=warn")],
+            options.status_map
+        );
+        assert_eq!(1, options.retry.retries);
+        assert_eq!(TlsOptions::default(), options.tls);
+    }
+
+    #[test]
+    fn first_matching_host() {
+        let options = RequestOptions::new(
+            &config(),
+            "https://www.linkedin.com/in/someone",
+            &global_headers(),
+        );
+        assert_eq!(HttpMethod::Get, options.method);
+        assert_eq!(vec![status("999")], options.accept_status);
+        assert_eq!(
+            vec![
+                mapping("403=ok"),
+                mapping("401=ignore"),
+                mapping("403=warn")
+            ],
+            options.status_map
+        );
+
+        let options =
+            RequestOptions::new(&config(), "https://api.github.com/repos", &global_headers());
+        assert_eq!(
+            vec![
+                ("User-Agent".to_string(), "mlc".to_string()),
+                ("Authorization".to_string(), "Bearer token".to_string()),
+            ],
+            options.headers
+        );
+        assert_eq!(vec![status("4xx")], options.accept_status);
+        assert_eq!(3, options.retry.retries);
+        assert_eq!(Some(Duration::from_secs(5)), options.timeouts.total);
+        assert!(options.tls.accept_invalid_certs);
+        assert!(!options.tls.accept_invalid_hostnames);
+    }
+
+    #[test]
+    fn deserialize() {
+        let config: OptionalConfig = toml::from_str(
+            r#"
+retries = 1
+[[hosts]]
+host = "*.linkedin.com"
+accept-status = [999, "400-403"]
+status-map = ["5xx=warn"]
+method = "get"
+[[hosts]]
+host = "localhost"
+max-concurrency = 1
+throttle = 100
+accept-invalid-certs = true
+"#,
+        )
+        .unwrap();
+        let hosts = config.hosts.unwrap();
+        assert_eq!(
+            HostConfig {
+                host: "*.linkedin.com".to_string(),
+                accept_status: Some(vec![status("999"), status("400-403")]),
+                status_map: Some(vec![mapping("5xx=warn")]),
+                method: Some(HttpMethod::Get),
+                ..HostConfig::default()
+            },
+            hosts[0]
+        );
+        assert_eq!(Some(1), hosts[1].max_concurrency);
+        assert_eq!(Some(100), hosts[1].throttle);
+        assert_eq!(Some(true), hosts[1].accept_invalid_certs);
+    }
+
+    #[test]
+    fn headers() {
+        let headers = parse_http_headers(&[
+            "Authorization: Bearer a:b".to_string(),
+            "invalid".to_string(),
+        ]);
+        assert_eq!(
+            vec![("Authorization".to_string(), "Bearer a:b".to_string())],
+            headers
+        );
+    }
+}
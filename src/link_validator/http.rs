@@ -1,45 +1,222 @@
+use crate::link_extractors::html_link_extractor::HtmlLinkExtractor;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::slug::SlugStyle;
+use crate::link_validator::host::{HttpMethod, RequestOptions, TlsOptions};
+use crate::link_validator::retry::{is_transient_error, is_transient_status, retry_after};
+use crate::link_validator::status::StatusResult;
+use crate::link_validator::timeout::Timeouts;
 use crate::link_validator::LinkCheckResult;
+use crate::link_validator::Severity;
 
+use regex::Regex;
 use reqwest::header::ACCEPT;
+use reqwest::header::CONTENT_TYPE;
 use reqwest::header::USER_AGENT;
 use reqwest::Client;
 use reqwest::Method;
 use reqwest::Request;
 use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::OnceCell;
 use wildmatch::WildMatch;
 
 const BROWSER_ACCEPT_HEADER: &str =
     "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
 
+type AnchorCache = Mutex<HashMap<String, Arc<OnceCell<Option<Vec<String>>>>>>;
+type ClientCache = Mutex<HashMap<(Option<Duration>, Option<Duration>, TlsOptions), Client>>;
+
+lazy_static! {
+    /// Clients by connect timeout, read timeout and TLS options. They are
+    /// shared to reuse connections.
+    static ref CLIENTS: ClientCache = Mutex::new(HashMap::new());
+    /// Anchors of already downloaded web pages. The key is the URL without fragment.
+    static ref ANCHOR_CACHE: AnchorCache = Mutex::new(HashMap::new());
+}
+
+/// Client with the connect timeout, read timeout and TLS options
+fn client(timeouts: &Timeouts, tls: TlsOptions) -> Client {
+    CLIENTS
+        .lock()
+        .expect("Bug! client cache poisoned")
+        .entry((timeouts.connect, timeouts.read, tls))
+        .or_insert_with(|| {
+            let mut builder = Client::builder()
+                .brotli(true)
+                .gzip(true)
+                .deflate(true)
+                .tls_danger_accept_invalid_certs(tls.accept_invalid_certs)
+                .tls_danger_accept_invalid_hostnames(tls.accept_invalid_hostnames);
+            if let Some(connect) = timeouts.connect {
+                builder = builder.connect_timeout(connect);
+            }
+            if let Some(read) = timeouts.read {
+                builder = builder.read_timeout(read);
+            }
+            builder.build().expect("Bug! failed to build client")
+        })
+        .clone()
+}
+
+/// Result of a single request of a web link
+enum Attempt {
+    Done(LinkCheckResult),
+    /// Failure which may not occur if the request is repeated later
+    Transient {
+        result: LinkCheckResult,
+        retry_after: Option<Duration>,
+    },
+}
+
+/// Check the web link. Returns the result and the status code of the last
+/// response, which is `None` if the request failed without response.
 pub async fn check_http(
     target: &str,
     do_not_warn_for_redirect_to: &[WildMatch],
-    http_headers: &[(String, String)],
-) -> LinkCheckResult {
+    options: &RequestOptions,
+) -> (LinkCheckResult, Option<u16>) {
     debug!("Check http link target {target:?}");
     let url = reqwest::Url::parse(target).expect("URL of unknown type");
 
-    match http_request(&url, do_not_warn_for_redirect_to, http_headers).await {
+    let mut attempts = 1;
+    let mut waited = Duration::ZERO;
+    loop {
+        let attempt = http_request(&url, do_not_warn_for_redirect_to, options);
+        let (attempt, status) = attempt.await;
+        let (result, retry_after) = match attempt {
+            Attempt::Done(result) => return (with_attempts(result, attempts), status),
+            Attempt::Transient {
+                result,
+                retry_after,
+            } => (result, retry_after),
+        };
+        match options.retry.next_delay(attempts, waited, retry_after) {
+            Some(delay) => {
+                debug!("{result:?} Retry {target} in {delay:?}.");
+                tokio::time::sleep(delay).await;
+                waited += delay;
+                attempts += 1;
+            }
+            None => return (with_attempts(result, attempts), status),
+        }
+    }
+}
+
+/// Append the number of attempts to the message if the request was retried
+fn with_attempts(result: LinkCheckResult, attempts: u32) -> LinkCheckResult {
+    if attempts == 1 {
+        return result;
+    }
+    let append = |msg: String| match msg.strip_suffix('.') {
+        Some(msg) => format!("{msg} (after {attempts} attempts)."),
+        None => format!("{msg} (after {attempts} attempts)"),
+    };
+    match result {
+        LinkCheckResult::Failed(msg) => LinkCheckResult::Failed(append(msg)),
+        LinkCheckResult::Warning(msg) => LinkCheckResult::Warning(append(msg)),
+        result => result,
+    }
+}
+
+/// Check that the fragment of the target URL is an anchor of the web page
+pub async fn check_http_anchor(
+    target: &str,
+    severity: Severity,
+    ignore_anchors: &[WildMatch],
+    options: &RequestOptions,
+) -> LinkCheckResult {
+    let mut url = reqwest::Url::parse(target).expect("URL of unknown type");
+    let fragment = match url.fragment() {
+        Some(fragment) if !fragment.is_empty() => url_escape::decode(fragment).to_string(),
+        _ => return LinkCheckResult::Ok,
+    };
+    if is_special_fragment(&fragment) || ignore_anchors.iter().any(|m| m.matches(&fragment)) {
+        debug!("Skip check of anchor '{fragment}' in {target}");
+        return LinkCheckResult::Ok;
+    }
+    url.set_fragment(None);
+
+    let cell = ANCHOR_CACHE
+        .lock()
+        .expect("Bug! anchor cache poisoned")
+        .entry(url.to_string())
+        .or_default()
+        .clone();
+    let anchors = cell.get_or_init(|| fetch_anchors(&url, options)).await;
+
+    match anchors {
+        Some(anchors) => {
+            // GitHub prefixes the ids of rendered markdown headings
+            let github_anchor = format!("user-content-{fragment}");
+            if anchors
+                .iter()
+                .any(|anchor| anchor == &fragment || anchor == &github_anchor)
+            {
+                LinkCheckResult::Ok
+            } else {
+                severity.to_result(format!("Anchor '#{fragment}' not found on web page."))
+            }
+        }
+        None => LinkCheckResult::Ok,
+    }
+}
+
+/// Fragments which do not reference an html anchor. For example text fragments
+/// (`#:~:text=`) or line numbers of source files (`#L10` or `#L10-L20`).
+fn is_special_fragment(fragment: &str) -> bool {
+    lazy_static! {
+        static ref LINE_FRAGMENT_REGEX: Regex =
+            Regex::new(r"^L\d+(C\d+)?(-L\d+(C\d+)?)?$").unwrap();
+    }
+    fragment.starts_with(":~:") || LINE_FRAGMENT_REGEX.is_match(fragment)
+}
+
+/// Download the web page and collect all anchors. `None` if the page is no html page.
+async fn fetch_anchors(url: &reqwest::Url, options: &RequestOptions) -> Option<Vec<String>> {
+    debug!("Download {url} to check anchors");
+    let response = match client(&options.timeouts, options.tls)
+        .execute(new_request(Method::GET, url, options))
+        .await
+    {
         Ok(response) => response,
-        Err(error_msg) => LinkCheckResult::Failed(format!("Http(s) request failed. {error_msg}")),
+        Err(e) => {
+            warn!("Failed to download {url} to check anchors. {e}");
+            return None;
+        }
+    };
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("html"));
+    if !response.status().is_success() || !is_html {
+        debug!("Anchors of {url} are unknown. Skip check of anchors.");
+        return None;
     }
+    let text = response.text().await.ok()?;
+    HtmlLinkExtractor().find_anchors(&text, SlugStyle::default())
 }
 
-fn new_request(method: Method, url: &reqwest::Url, http_headers: &[(String, String)]) -> Request {
+fn new_request(method: Method, url: &reqwest::Url, options: &RequestOptions) -> Request {
     let mut req = Request::new(method, url.clone());
+    *req.timeout_mut() = options.timeouts.total;
     let headers = req.headers_mut();
     headers.insert(ACCEPT, BROWSER_ACCEPT_HEADER.parse().unwrap());
 
     // Set default user agent if no custom User-Agent is provided
-    let has_custom_user_agent = http_headers
+    let has_custom_user_agent = options
+        .headers
         .iter()
         .any(|(k, _)| k.to_lowercase() == "user-agent");
     if !has_custom_user_agent {
         headers.insert(USER_AGENT, "mlc (github.com/becheran/mlc)".parse().unwrap());
     }
 
-    // Apply custom headers
-    for (key, value) in http_headers {
+    // Apply custom headers. Later headers replace earlier ones of the same name.
+    for (key, value) in &options.headers {
         if let (Ok(header_name), Ok(header_value)) = (
             reqwest::header::HeaderName::from_bytes(key.as_bytes()),
             reqwest::header::HeaderValue::from_str(value),
@@ -56,17 +233,37 @@ fn new_request(method: Method, url: &reqwest::Url, http_headers: &[(String, Stri
 async fn http_request(
     url: &reqwest::Url,
     do_not_warn_for_redirect_to: &[WildMatch],
-    http_headers: &[(String, String)],
-) -> reqwest::Result<LinkCheckResult> {
-    lazy_static! {
-        static ref CLIENT: Client = reqwest::Client::builder()
-            .brotli(true)
-            .gzip(true)
-            .deflate(true)
-            .build()
-            .expect("Bug! failed to build client");
+    options: &RequestOptions,
+) -> (Attempt, Option<u16>) {
+    match http_request_once(url, do_not_warn_for_redirect_to, options).await {
+        Ok((attempt, status)) => (attempt, Some(status)),
+        Err(error) => {
+            let timeouts = &options.timeouts;
+            let result = if error.is_timeout() {
+                timeouts.severity.to_result(format!(
+                    "Timeout. No response within the configured time ({timeouts})."
+                ))
+            } else {
+                LinkCheckResult::Failed(format!("Http(s) request failed. {error}"))
+            };
+            let attempt = if is_transient_error(&error) {
+                Attempt::Transient {
+                    result,
+                    retry_after: None,
+                }
+            } else {
+                Attempt::Done(result)
+            };
+            (attempt, None)
+        }
     }
+}
 
+async fn http_request_once(
+    url: &reqwest::Url,
+    do_not_warn_for_redirect_to: &[WildMatch],
+    options: &RequestOptions,
+) -> reqwest::Result<(Attempt, u16)> {
     fn status_to_string(status: StatusCode) -> String {
         format!(
             "{} - {}",
@@ -75,10 +272,26 @@ async fn http_request(
         )
     }
 
-    let response = CLIENT
-        .execute(new_request(Method::HEAD, url, http_headers))
-        .await?;
-    let check_redirect = |response_url: &reqwest::Url| -> reqwest::Result<LinkCheckResult> {
+    // The first matching entry of the status map decides the result
+    let mapped = |status: StatusCode| {
+        options
+            .status_map
+            .iter()
+            .find(|mapping| mapping.status.contains(status.as_u16()))
+            .map(|mapping| mapping.result)
+    };
+    let is_accepted = |status: StatusCode| match mapped(status) {
+        Some(result) => result == StatusResult::Ok,
+        None => {
+            status.is_success()
+                || status.is_redirection()
+                || options
+                    .accept_status
+                    .iter()
+                    .any(|range| range.contains(status.as_u16()))
+        }
+    };
+    let check_redirect = |response_url: &reqwest::Url| -> Attempt {
         // Compare URLs ignoring fragments since fragments are not sent to the server
         // and the response URL will never have them
         let urls_match = url.scheme() == response_url.scheme()
@@ -92,34 +305,55 @@ async fn http_request(
                 .iter()
                 .any(|x| x.matches(response_url.as_ref()))
         {
-            Ok(LinkCheckResult::Ok)
+            Attempt::Done(LinkCheckResult::Ok)
         } else {
-            Ok(LinkCheckResult::Warning(
+            Attempt::Done(LinkCheckResult::Warning(
                 "Request was redirected to ".to_string() + response_url.as_ref(),
             ))
         }
     };
 
-    let status = response.status();
-    if status.is_success() || status.is_redirection() {
-        check_redirect(response.url())
-    } else {
-        debug!("Got the status code {status:?}. Retry with get-request.");
-        let get_request = new_request(Method::GET, url, http_headers);
-
-        let response = CLIENT.execute(get_request).await?;
+    let client = client(&options.timeouts, options.tls);
+    if options.method == HttpMethod::Head {
+        let response = client
+            .execute(new_request(Method::HEAD, url, options))
+            .await?;
         let status = response.status();
-        if status.is_success() || status.is_redirection() {
-            check_redirect(response.url())
-        } else {
-            Ok(LinkCheckResult::Failed(status_to_string(status)))
+        if is_accepted(status) {
+            return Ok((check_redirect(response.url()), status.as_u16()));
         }
+        debug!("Got the status code {status:?}. Retry with get-request.");
     }
+
+    let response = client
+        .execute(new_request(Method::GET, url, options))
+        .await?;
+    let status = response.status();
+    let attempt = match mapped(status) {
+        Some(StatusResult::Ok) => check_redirect(response.url()),
+        Some(StatusResult::Warn) => {
+            Attempt::Done(LinkCheckResult::Warning(status_to_string(status)))
+        }
+        Some(StatusResult::Ignore) => {
+            Attempt::Done(LinkCheckResult::Ignored(status_to_string(status)))
+        }
+        Some(StatusResult::Error) => {
+            Attempt::Done(LinkCheckResult::Failed(status_to_string(status)))
+        }
+        None if is_accepted(status) => check_redirect(response.url()),
+        None if is_transient_status(status) => Attempt::Transient {
+            result: LinkCheckResult::Failed(status_to_string(status)),
+            retry_after: retry_after(response.headers()),
+        },
+        None => Attempt::Done(LinkCheckResult::Failed(status_to_string(status))),
+    };
+    Ok((attempt, status.as_u16()))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::link_validator::retry::RetryPolicy;
 
     #[tokio::test]
     async fn check_http_is_available() {
@@ -130,7 +364,7 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(&server.url(), &[], &[]).await;
+        let (result, _) = check_http(&server.url(), &[], &RequestOptions::default()).await;
         assert_eq!(result, LinkCheckResult::Ok);
     }
 
@@ -143,11 +377,12 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(&server.url(), &[], &[]).await;
+        let (result, status) = check_http(&server.url(), &[], &RequestOptions::default()).await;
         assert_eq!(
             result,
             LinkCheckResult::Failed("500 - Internal Server Error".to_string())
         );
+        assert_eq!(status, Some(500));
     }
 
     #[tokio::test]
@@ -167,7 +402,7 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(&server.url(), &[], &[]).await;
+        let (result, _) = check_http(&server.url(), &[], &RequestOptions::default()).await;
         assert_eq!(
             result,
             LinkCheckResult::Warning(format!(
@@ -193,10 +428,10 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(
+        let (result, _) = check_http(
             &server.url(),
             &[WildMatch::new(&format!("{}*", &redirect_server.url()))],
-            &[],
+            &RequestOptions::default(),
         )
         .await;
 
@@ -219,7 +454,12 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(&server.url(), &[WildMatch::new("*")], &[]).await;
+        let (result, _) = check_http(
+            &server.url(),
+            &[WildMatch::new("*")],
+            &RequestOptions::default(),
+        )
+        .await;
 
         assert_eq!(result, LinkCheckResult::Ok);
     }
@@ -240,10 +480,10 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(
+        let (result, _) = check_http(
             &server.url(),
             &[WildMatch::new("http://is-mismatched.com/*")],
-            &[],
+            &RequestOptions::default(),
         )
         .await;
 
@@ -272,7 +512,7 @@ mod test {
             .create_async()
             .await;
 
-        let result = check_http(&server.url(), &[], &[]).await;
+        let (result, _) = check_http(&server.url(), &[], &RequestOptions::default()).await;
 
         assert_eq!(
             result,
@@ -292,7 +532,7 @@ mod test {
         // The URL with a fragment should not produce a redirect warning
         // because the fragment is not sent to the server
         let url_with_fragment = format!("{}/page#anchor", server.url());
-        let result = check_http(&url_with_fragment, &[], &[]).await;
+        let (result, _) = check_http(&url_with_fragment, &[], &RequestOptions::default()).await;
         assert_eq!(result, LinkCheckResult::Ok);
     }
 
@@ -316,7 +556,7 @@ mod test {
         // A real redirect to a different page should still produce a warning
         // even if the original URL had a fragment
         let url_with_fragment = format!("{}/page#anchor", server.url());
-        let result = check_http(&url_with_fragment, &[], &[]).await;
+        let (result, _) = check_http(&url_with_fragment, &[], &RequestOptions::default()).await;
         assert_eq!(
             result,
             LinkCheckResult::Warning(format!(
@@ -341,7 +581,400 @@ mod test {
             ("User-Agent".to_string(), "CustomAgent/1.0".to_string()),
             ("X-Custom-Header".to_string(), "test-value".to_string()),
         ];
-        let result = check_http(&server.url(), &[], &custom_headers).await;
+        let options = RequestOptions {
+            headers: custom_headers,
+            ..RequestOptions::default()
+        };
+        let (result, _) = check_http(&server.url(), &[], &options).await;
         assert_eq!(result, LinkCheckResult::Ok);
     }
+
+    #[tokio::test]
+    async fn check_http_accept_status() {
+        let mut server = mockito::Server::new_async().await;
+        for method in ["HEAD", "GET"] {
+            server
+                .mock(method, "/")
+                .with_status(999)
+                .create_async()
+                .await;
+        }
+
+        let options = RequestOptions {
+            accept_status: vec!["999".parse().unwrap()],
+            ..RequestOptions::default()
+        };
+        let (result, _) = check_http(&server.url(), &[], &options).await;
+        assert_eq!(result, LinkCheckResult::Ok);
+
+        let (result, _) = check_http(&server.url(), &[], &RequestOptions::default()).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("999 - Unknown reason".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_http_status_map() {
+        let mut server = mockito::Server::new_async().await;
+        for (path, status) in [
+            ("/login", 401),
+            ("/bot", 403),
+            ("/error", 503),
+            ("/ok", 200),
+            ("/gone", 404),
+        ] {
+            server
+                .mock("GET", path)
+                .with_status(status)
+                .create_async()
+                .await;
+        }
+
+        let options = RequestOptions {
+            status_map: ["401=ignore", "403=warn", "5xx=error", "2xx=warn", "4xx=ok"]
+                .iter()
+                .map(|mapping| mapping.parse().unwrap())
+                .collect(),
+            ..retry_options(3)
+        };
+        let check = |path: &str| {
+            let url = format!("{}{path}", server.url());
+            let options = options.clone();
+            async move { check_http(&url, &[], &options).await }
+        };
+        assert_eq!(
+            check("/login").await,
+            (
+                LinkCheckResult::Ignored("401 - Unauthorized".to_string()),
+                Some(401)
+            )
+        );
+        assert_eq!(
+            check("/bot").await,
+            (
+                LinkCheckResult::Warning("403 - Forbidden".to_string()),
+                Some(403)
+            )
+        );
+        // Mapped status codes are not retried
+        assert_eq!(
+            check("/error").await,
+            (
+                LinkCheckResult::Failed("503 - Service Unavailable".to_string()),
+                Some(503)
+            )
+        );
+        assert_eq!(
+            check("/ok").await,
+            (LinkCheckResult::Warning("200 - OK".to_string()), Some(200))
+        );
+        assert_eq!(check("/gone").await, (LinkCheckResult::Ok, Some(404)));
+    }
+
+    #[tokio::test]
+    async fn check_http_accept_status_range() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/")
+            .with_status(429)
+            .create_async()
+            .await;
+
+        let options = RequestOptions {
+            accept_status: vec!["4xx".parse().unwrap()],
+            ..RequestOptions::default()
+        };
+        let result = check_http(&server.url(), &[], &options).await;
+        assert_eq!(result, (LinkCheckResult::Ok, Some(429)));
+    }
+
+    #[tokio::test]
+    async fn check_http_get_method() {
+        let mut server = mockito::Server::new_async().await;
+        let head = server
+            .mock("HEAD", "/")
+            .with_status(200)
+            .expect(0)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let options = RequestOptions {
+            method: HttpMethod::Get,
+            ..RequestOptions::default()
+        };
+        let (result, _) = check_http(&server.url(), &[], &options).await;
+        assert_eq!(result, LinkCheckResult::Ok);
+        head.assert_async().await;
+    }
+
+    fn retry_options(retries: u32) -> RequestOptions {
+        RequestOptions {
+            retry: RetryPolicy {
+                retries,
+                delay: Duration::from_millis(1),
+                max_wait: Duration::from_secs(1),
+            },
+            ..RequestOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn check_http_retry_transient_failure() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/")
+            .with_status(503)
+            .with_header("Retry-After", "0")
+            .expect(2)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/")
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let (result, _) = check_http(&server.url(), &[], &retry_options(3)).await;
+        assert_eq!(result, LinkCheckResult::Ok);
+        unavailable.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn check_http_retry_gives_up() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .with_status(502)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let (result, _) = check_http(&server.url(), &[], &retry_options(2)).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("502 - Bad Gateway (after 3 attempts)".to_string())
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn check_http_retry_after_exceeds_max_wait() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .with_status(429)
+            .with_header("Retry-After", "120")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let (result, _) = check_http(&server.url(), &[], &retry_options(3)).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("429 - Too Many Requests".to_string())
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn check_http_no_retry_of_permanent_failure() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let (result, _) = check_http(&server.url(), &[], &retry_options(3)).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("404 - Not Found".to_string())
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn check_http_no_retry_of_unresolvable_host() {
+        let (result, status) =
+            check_http("http://does-not-exist.invalid/", &[], &retry_options(3)).await;
+        assert_eq!(status, None);
+        match result {
+            LinkCheckResult::Failed(msg) => assert!(!msg.contains("attempts"), "{}", msg),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn check_http_retry_refused_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let (result, _) = check_http(&url, &[], &retry_options(1)).await;
+        match result {
+            LinkCheckResult::Failed(msg) => {
+                assert!(msg.ends_with("(after 2 attempts)"), "{}", msg)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    /// URL of a server which accepts connections but never responds
+    async fn unresponsive_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = vec![];
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        format!("http://{addr}/")
+    }
+
+    #[tokio::test]
+    async fn check_http_timeout() {
+        let url = unresponsive_server().await;
+        let options = RequestOptions {
+            timeouts: Timeouts {
+                connect: None,
+                read: None,
+                total: Some(Duration::from_millis(100)),
+                severity: Severity::Error,
+            },
+            ..RequestOptions::default()
+        };
+
+        let (result, status) = check_http(&url, &[], &options).await;
+        assert_eq!(status, None);
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed(
+                "Timeout. No response within the configured time (total 100 ms).".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn check_http_read_timeout_warning() {
+        let url = unresponsive_server().await;
+        let options = RequestOptions {
+            timeouts: Timeouts {
+                connect: Some(Duration::from_secs(1)),
+                read: Some(Duration::from_millis(100)),
+                total: None,
+                severity: Severity::Warn,
+            },
+            ..retry_options(1)
+        };
+
+        let (result, _) = check_http(&url, &[], &options).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Warning(
+                "Timeout. No response within the configured time (connect 1000 ms, read 100 ms) (after 2 attempts)."
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn check_http_anchor_found() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/found")
+            .with_status(200)
+            .with_header("content-type", "text/html; charset=utf-8")
+            .with_body("<h2 id=\"usage\">Usage</h2><h2 id=\"user-content-setup\">Setup</h2>")
+            .create_async()
+            .await;
+
+        for anchor in ["usage", "setup"] {
+            let url = format!("{}/found#{anchor}", server.url());
+            let result =
+                check_http_anchor(&url, Severity::Error, &[], &RequestOptions::default()).await;
+            assert_eq!(result, LinkCheckResult::Ok);
+        }
+    }
+
+    #[tokio::test]
+    async fn check_http_anchor_missing() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/missing")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body("<h2 id=\"usage\">Usage</h2>")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let url = format!("{}/missing#instalation", server.url());
+        let result =
+            check_http_anchor(&url, Severity::Error, &[], &RequestOptions::default()).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("Anchor '#instalation' not found on web page.".to_string())
+        );
+
+        let url = format!("{}/missing#other", server.url());
+        let result = check_http_anchor(&url, Severity::Warn, &[], &RequestOptions::default()).await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Warning("Anchor '#other' not found on web page.".to_string())
+        );
+
+        // The page is only downloaded once
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn check_http_anchor_not_html() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/data.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{\"id\": \"usage\"}")
+            .create_async()
+            .await;
+
+        let url = format!("{}/data.json#missing", server.url());
+        let result =
+            check_http_anchor(&url, Severity::Error, &[], &RequestOptions::default()).await;
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn check_http_anchor_ignored() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/file.rs")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body("<p>code</p>")
+            .expect(0)
+            .create_async()
+            .await;
+
+        for fragment in ["L10", "L10-L20", ":~:text=code", "custom-ignored"] {
+            let url = format!("{}/file.rs#{fragment}", server.url());
+            let result = check_http_anchor(
+                &url,
+                Severity::Error,
+                &[WildMatch::new("custom-*")],
+                &RequestOptions::default(),
+            )
+            .await;
+            assert_eq!(result, LinkCheckResult::Ok);
+        }
+        mock.assert_async().await;
+    }
 }
@@ -1,14 +1,24 @@
 mod file_system;
+pub mod host;
 mod http;
 mod mail;
+mod retry;
+pub mod status;
+mod timeout;
+pub mod wiki_link;
 
 pub mod link_type;
 
 use crate::link_extractors::link_extractor::MarkupLink;
 use crate::link_validator::file_system::check_filesystem;
 use crate::link_validator::http::check_http;
+use crate::link_validator::http::check_http_anchor;
 use crate::Config;
+use host::RequestOptions;
 use mail::check_mail;
+use serde::Deserialize;
+use std::str::FromStr;
+use url::{ParseError, Url};
 
 pub use link_type::get_link_type;
 pub use link_type::LinkType;
@@ -23,15 +33,81 @@ pub enum LinkCheckResult {
     NotImplemented(String),
 }
 
-pub async fn resolve_target_link(
-    link: &MarkupLink,
-    link_type: &LinkType,
-    config: &Config,
-) -> String {
-    if link_type == &LinkType::FileSystem {
-        file_system::resolve_target_link(&link.source, &link.target, config).await
+/// Severity with which a problem found during a link check is reported
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warn,
+    Error,
+}
+
+impl FromStr for Severity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Severity, ()> {
+        match s {
+            "warn" => Ok(Severity::Warn),
+            "error" => Ok(Severity::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Severity {
+    #[must_use]
+    pub fn to_result(self, msg: String) -> LinkCheckResult {
+        match self {
+            Severity::Warn => LinkCheckResult::Warning(msg),
+            Severity::Error => LinkCheckResult::Failed(msg),
+        }
+    }
+}
+
+/// Resolve the target of a link, applying the base URL of its document
+pub async fn resolve_target_link(link: &MarkupLink, config: &Config) -> (String, LinkType) {
+    let target = match &link.base {
+        Some(base) => apply_base(base, &link.target),
+        None => link.target.to_string(),
+    };
+    let link_type = get_link_type(&target);
+    let target = if link_type == LinkType::FileSystem {
+        file_system::resolve_target_link(&link.source, &target, config).await
     } else {
-        link.target.to_string()
+        target
+    };
+    (target, link_type)
+}
+
+/// Resolve a relative target against a base URL.
+///
+/// Absolute base URLs turn relative targets into URLs. Base paths are
+/// prepended to relative targets, which are then resolved on the file system.
+fn apply_base(base: &str, target: &str) -> String {
+    if Url::parse(target) != Err(ParseError::RelativeUrlWithoutBase) {
+        return target.to_string();
+    }
+    let base = match base.strip_prefix("//") {
+        Some(rest) => format!("https://{rest}"),
+        None => base.to_string(),
+    };
+    match Url::parse(&base) {
+        Ok(base_url) => match base_url.join(target) {
+            Ok(url) => url.to_string(),
+            Err(e) => {
+                warn!("Could not apply base URL '{base}' to '{target}'. {e}");
+                target.to_string()
+            }
+        },
+        Err(_) if target.starts_with('/') || target.starts_with('\\') => target.to_string(),
+        Err(_) if target.starts_with('#') => {
+            let base = base.split('#').next().unwrap_or_default();
+            format!("{base}{target}")
+        }
+        Err(_) => {
+            let base = base.split(['#', '?']).next().unwrap_or_default();
+            let dir = &base[..base.rfind('/').map_or(0, |idx| idx + 1)];
+            format!("{dir}{target}")
+        }
     }
 }
 
@@ -41,9 +117,9 @@ pub async fn check(
     config: &Config,
     do_not_warn_for_redirect_to: &[WildMatch],
     http_headers: &[(String, String)],
-) -> LinkCheckResult {
+) -> (LinkCheckResult, Option<u16>) {
     info!("Check link {}.", &link_target);
-    match link_type {
+    let result = match link_type {
         LinkType::Ftp => LinkCheckResult::NotImplemented(format!(
             "Link type '{:?}' is not supported yet...",
             &link_target
@@ -56,9 +132,101 @@ pub async fn check(
             if config.optional.offline.unwrap_or_default() {
                 LinkCheckResult::Ignored("Ignore web link because of the offline flag.".to_string())
             } else {
-                check_http(link_target, do_not_warn_for_redirect_to, http_headers).await
+                let options = RequestOptions::new(&config.optional, link_target, http_headers);
+                let (result, status) =
+                    check_http(link_target, do_not_warn_for_redirect_to, &options).await;
+                let result = match (&result, config.optional.check_web_anchors) {
+                    (LinkCheckResult::Ok, Some(severity)) => {
+                        let ignore_anchors: Vec<WildMatch> = match &config.optional.ignore_anchors {
+                            Some(s) => s.iter().map(|m| WildMatch::new(m)).collect(),
+                            None => vec![],
+                        };
+                        check_http_anchor(link_target, severity, &ignore_anchors, &options).await
+                    }
+                    _ => result,
+                };
+                return (result, status);
             }
         }
         LinkType::FileSystem => check_filesystem(link_target, config).await,
+    };
+    (result, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+    use std::path::Path;
+
+    #[test_case(
+        "https://example.com/docs/",
+        "page.html",
+        "https://example.com/docs/page.html"
+    )]
+    #[test_case(
+        "https://example.com/docs/index.html",
+        "../img/a.png",
+        "https://example.com/img/a.png"
+    )]
+    #[test_case(
+        "https://example.com/docs/",
+        "/root.html",
+        "https://example.com/root.html"
+    )]
+    #[test_case("https://example.com/docs/", "#top", "https://example.com/docs/#top")]
+    #[test_case(
+        "//cdn.example.com/assets/",
+        "app.js",
+        "https://cdn.example.com/assets/app.js"
+    )]
+    #[test_case(
+        "https://example.com/docs/",
+        "https://other.com/",
+        "https://other.com/"
+    )]
+    #[test_case("https://example.com/docs/", "mailto:a@b.com", "mailto:a@b.com")]
+    #[test_case("/docs/", "page.html", "/docs/page.html")]
+    #[test_case("../", "page.html", "../page.html")]
+    #[test_case("../docs/index.html?x=1", "page.html", "../docs/page.html")]
+    #[test_case("docs/index.html", "#top", "docs/index.html#top")]
+    #[test_case("/docs/", "/root.html", "/root.html")]
+    #[test_case("index.html", "page.html", "page.html")]
+    fn base(base: &str, target: &str, expected: &str) {
+        assert_eq!(apply_base(base, target), expected);
+    }
+
+    #[tokio::test]
+    async fn resolve_with_base() {
+        let link = |target: &str, base: &str| MarkupLink {
+            source: "./benches/benchmark/html/anchors.html".to_string(),
+            target: target.to_string(),
+            line: 1,
+            column: 1,
+            location: None,
+            base: Some(base.to_string()),
+            from_code: false,
+            wiki_link: false,
+        };
+        let config = Config::default();
+
+        let (target, link_type) =
+            resolve_target_link(&link("page.html", "https://example.com/"), &config).await;
+        assert_eq!(target, "https://example.com/page.html");
+        assert_eq!(link_type, LinkType::Http);
+
+        let (target, link_type) =
+            resolve_target_link(&link("HashLinks.md", "../markdown/"), &config).await;
+        assert_eq!(link_type, LinkType::FileSystem);
+        assert!(
+            target.ends_with(
+                &Path::new("markdown")
+                    .join("HashLinks.md")
+                    .display()
+                    .to_string()
+            ),
+            "{}",
+            target
+        );
     }
 }
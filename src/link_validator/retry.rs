@@ -0,0 +1,219 @@
+use crate::link_validator::host::HostConfig;
+use crate::OptionalConfig;
+use reqwest::header::HeaderMap;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use std::io::ErrorKind;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_RETRY_DELAY_MS: u64 = 1000;
+const DEFAULT_RETRY_MAX_WAIT_MS: u64 = 60_000;
+
+/// How often and how long transient failures of web requests are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub retries: u32,
+    /// Delay before the first retry. It is doubled with every further retry.
+    pub delay: Duration,
+    /// Maximum time waited in between all attempts of one request
+    pub max_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            retries: 0,
+            delay: Duration::from_millis(DEFAULT_RETRY_DELAY_MS),
+            max_wait: Duration::from_millis(DEFAULT_RETRY_MAX_WAIT_MS),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry policy of the global options, overridden by the host block
+    #[must_use]
+    pub fn new(config: &OptionalConfig, host: Option<&HostConfig>) -> Self {
+        let retries = host.and_then(|h| h.retries).or(config.retries);
+        let delay = host.and_then(|h| h.retry_delay).or(config.retry_delay);
+        let max_wait = host
+            .and_then(|h| h.retry_max_wait)
+            .or(config.retry_max_wait);
+        RetryPolicy {
+            retries: retries.unwrap_or_default(),
+            delay: Duration::from_millis(delay.unwrap_or(DEFAULT_RETRY_DELAY_MS)),
+            max_wait: Duration::from_millis(max_wait.unwrap_or(DEFAULT_RETRY_MAX_WAIT_MS)),
+        }
+    }
+
+    /// Time to wait before the next attempt after `attempts` failed ones, or
+    /// `None` if the request shall not be retried anymore. A `Retry-After` of
+    /// the server is used as is, otherwise the delay grows exponentially with
+    /// a random jitter so that parallel requests do not retry in lockstep.
+    #[must_use]
+    pub fn next_delay(
+        &self,
+        attempts: u32,
+        waited: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempts > self.retries {
+            return None;
+        }
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => {
+                let backoff = self
+                    .delay
+                    .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)));
+                backoff / 2 + backoff.mul_f64(fastrand::f64() / 2.0)
+            }
+        };
+        if waited + delay > self.max_wait {
+            None
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+/// Status codes of responses which may succeed if the request is repeated later
+#[must_use]
+pub fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Errors of requests which may succeed if the request is repeated later,
+/// like timeouts or connections reset by the server. Failed name resolutions
+/// and TLS errors are permanent.
+#[must_use]
+pub fn is_transient_error(error: &reqwest::Error) -> bool {
+    if error.is_timeout() {
+        return true;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(io_error) = err.downcast_ref::<std::io::Error>() {
+            if matches!(
+                io_error.kind(),
+                ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionAborted
+            ) {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Time to wait according to the `Retry-After` header, given in seconds or as
+/// HTTP-date
+#[must_use]
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+    use reqwest::header::HeaderValue;
+
+    fn policy(retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            retries,
+            delay: Duration::from_millis(100),
+            max_wait: Duration::from_millis(1000),
+        }
+    }
+
+    #[test]
+    fn exponential_backoff_with_jitter() {
+        let policy = policy(3);
+        for (attempts, max) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = policy.next_delay(attempts, Duration::ZERO, None).unwrap();
+            assert!(delay >= Duration::from_millis(max / 2), "{:?}", delay);
+            assert!(delay <= Duration::from_millis(max), "{:?}", delay);
+        }
+        assert_eq!(None, policy.next_delay(4, Duration::ZERO, None));
+    }
+
+    #[test]
+    fn retry_after_is_used() {
+        let delay = policy(1).next_delay(1, Duration::ZERO, Some(Duration::from_millis(700)));
+        assert_eq!(Some(Duration::from_millis(700)), delay);
+    }
+
+    #[test]
+    fn max_wait() {
+        let policy = policy(10);
+        assert_eq!(
+            None,
+            policy.next_delay(
+                1,
+                Duration::from_millis(950),
+                Some(Duration::from_millis(100))
+            )
+        );
+        assert_eq!(
+            None,
+            policy.next_delay(1, Duration::ZERO, Some(Duration::from_secs(2)))
+        );
+    }
+
+    #[test]
+    fn no_retries() {
+        assert_eq!(
+            None,
+            RetryPolicy::default().next_delay(1, Duration::ZERO, None)
+        );
+    }
+
+    #[test_case("120", 120)]
+    #[test_case(" 0 ", 0)]
+    #[test_case("Wed, 21 Oct 2015 07:28:00 GMT", 0)]
+    fn retry_after_header(value: &str, seconds: u64) {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        assert_eq!(Some(Duration::from_secs(seconds)), retry_after(&headers));
+    }
+
+    #[test]
+    fn retry_after_future_date() {
+        let date = SystemTime::now() + Duration::from_secs(60);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_str(&httpdate::fmt_http_date(date)).unwrap(),
+        );
+        let delay = retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(55), "{:?}", delay);
+        assert!(delay <= Duration::from_secs(60), "{:?}", delay);
+    }
+
+    #[test_case("soon")]
+    #[test_case("-1")]
+    fn invalid_retry_after(value: &str) {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        assert_eq!(None, retry_after(&headers));
+    }
+}
@@ -0,0 +1,209 @@
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Range of http status codes like `403`, `500-599` or `4xx`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "StatusRangeValue")]
+pub struct StatusRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+/// Status ranges are given as number or string in the config file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatusRangeValue {
+    Code(u16),
+    Range(String),
+}
+
+impl TryFrom<StatusRangeValue> for StatusRange {
+    type Error = String;
+
+    fn try_from(value: StatusRangeValue) -> Result<StatusRange, String> {
+        let range = match value {
+            StatusRangeValue::Code(code) => code.to_string(),
+            StatusRangeValue::Range(range) => range,
+        };
+        range
+            .parse()
+            .map_err(|_| format!("Invalid status range '{range}'."))
+    }
+}
+
+impl FromStr for StatusRange {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<StatusRange, ()> {
+        let s = s.trim();
+        let (start, end) = if let Some(class) = s.strip_suffix("xx") {
+            let class: u16 = class.parse().map_err(|_| ())?;
+            if !(1..=9).contains(&class) {
+                return Err(());
+            }
+            let start = class.checked_mul(100).ok_or(())?;
+            (start, start.checked_add(99).ok_or(())?)
+        } else if let Some((start, end)) = s.split_once('-') {
+            let start: u16 = start.trim().parse().map_err(|_| ())?;
+            let end: u16 = end.trim().parse().map_err(|_| ())?;
+            if start > end {
+                return Err(());
+            }
+            (start, end)
+        } else {
+            let code = s.parse().map_err(|_| ())?;
+            (code, code)
+        };
+        if (100..=999).contains(&start) && (start..=999).contains(&end) {
+            Ok(StatusRange { start, end })
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for StatusRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl StatusRange {
+    #[must_use]
+    pub fn contains(&self, status: u16) -> bool {
+        (self.start..=self.end).contains(&status)
+    }
+}
+
+/// How a link whose web server responds with a status code is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusResult {
+    Ok,
+    Warn,
+    Ignore,
+    Error,
+}
+
+impl FromStr for StatusResult {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<StatusResult, ()> {
+        match s.trim() {
+            "ok" => Ok(StatusResult::Ok),
+            "warn" => Ok(StatusResult::Warn),
+            "ignore" => Ok(StatusResult::Ignore),
+            "error" => Ok(StatusResult::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Result of the links whose web server responds with a status of the range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct StatusMapping {
+    pub status: StatusRange,
+    pub result: StatusResult,
+}
+
+impl TryFrom<String> for StatusMapping {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<StatusMapping, String> {
+        value
+            .parse()
+            .map_err(|_| format!("Invalid status mapping '{value}'. Expected 'STATUS=RESULT'."))
+    }
+}
+
+impl FromStr for StatusMapping {
+    type Err = ();
+
+    /// Parse a mapping of the form `STATUS=RESULT`, like `403=warn` or `5xx=ignore`
+    fn from_str(s: &str) -> Result<StatusMapping, ()> {
+        let (status, result) = s.split_once('=').ok_or(())?;
+        Ok(StatusMapping {
+            status: status.parse()?,
+            result: result.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("403", 403, 403)]
+    #[test_case("500-599", 500, 599)]
+    #[test_case(" 200 - 299 ", 200, 299)]
+    #[test_case("4xx", 400, 499)]
+    fn status_range(input: &str, start: u16, end: u16) {
+        assert_eq!(Ok(StatusRange { start, end }), input.parse());
+    }
+
+    #[test_case("")]
+    #[test_case("abc")]
+    #[test_case("99")]
+    #[test_case("1000")]
+    #[test_case("500-400")]
+    #[test_case("10xx")]
+    #[test_case("0xx")]
+    #[test_case("700xx")]
+    #[test_case("65535xx")]
+    #[test_case("404-403")]
+    fn invalid_status_range(input: &str) {
+        assert_eq!(Err(()), input.parse::<StatusRange>());
+    }
+
+    #[test]
+    fn status_mapping() {
+        assert_eq!(
+            Ok(StatusMapping {
+                status: StatusRange {
+                    start: 500,
+                    end: 599
+                },
+                result: StatusResult::Ignore,
+            }),
+            "5xx=ignore".parse()
+        );
+        assert_eq!(Err(()), "403".parse::<StatusMapping>());
+        assert_eq!(Err(()), "403=fine".parse::<StatusMapping>());
+    }
+
+    #[test]
+    fn deserialize() {
+        #[derive(Deserialize)]
+        struct Config {
+            accept: Vec<StatusRange>,
+            map: Vec<StatusMapping>,
+        }
+        let config: Config =
+            toml::from_str("accept = [999, \"200-299\"]\nmap = [\"401=ignore\"]").unwrap();
+        assert_eq!(
+            vec![
+                StatusRange {
+                    start: 999,
+                    end: 999
+                },
+                StatusRange {
+                    start: 200,
+                    end: 299
+                }
+            ],
+            config.accept
+        );
+        assert_eq!("401", config.map[0].status.to_string());
+        assert_eq!(StatusResult::Ignore, config.map[0].result);
+        assert!(toml::from_str::<Config>("accept = [\"x\"]\nmap = []").is_err());
+        assert!(toml::from_str::<Config>("accept = [99]\nmap = []").is_err());
+    }
+}
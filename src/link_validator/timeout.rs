@@ -0,0 +1,139 @@
+use crate::link_validator::host::HostConfig;
+use crate::link_validator::Severity;
+use crate::OptionalConfig;
+use std::fmt;
+use std::time::Duration;
+
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_READ_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_TIMEOUT_MS: u64 = 60_000;
+
+/// Timeouts of a web request. `None` means that there is no timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Maximum time to establish the connection
+    pub connect: Option<Duration>,
+    /// Maximum time in between two reads of the response
+    pub read: Option<Duration>,
+    /// Maximum time of the whole request
+    pub total: Option<Duration>,
+    /// Severity of a timed out request
+    pub severity: Severity,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            connect: millis(DEFAULT_CONNECT_TIMEOUT_MS),
+            read: millis(DEFAULT_READ_TIMEOUT_MS),
+            total: millis(DEFAULT_TIMEOUT_MS),
+            severity: Severity::Error,
+        }
+    }
+}
+
+impl Timeouts {
+    /// Timeouts of the global options, overridden by the host block
+    #[must_use]
+    pub fn new(config: &OptionalConfig, host: Option<&HostConfig>) -> Self {
+        let timeout = |host: Option<u64>, global: Option<u64>, default: u64| {
+            millis(host.or(global).unwrap_or(default))
+        };
+        Timeouts {
+            connect: timeout(
+                host.and_then(|h| h.connect_timeout),
+                config.connect_timeout,
+                DEFAULT_CONNECT_TIMEOUT_MS,
+            ),
+            read: timeout(
+                host.and_then(|h| h.read_timeout),
+                config.read_timeout,
+                DEFAULT_READ_TIMEOUT_MS,
+            ),
+            total: timeout(
+                host.and_then(|h| h.timeout),
+                config.timeout,
+                DEFAULT_TIMEOUT_MS,
+            ),
+            severity: config.timeout_severity.unwrap_or(Severity::Error),
+        }
+    }
+}
+
+impl fmt::Display for Timeouts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let timeouts: Vec<String> = [
+            ("connect", self.connect),
+            ("read", self.read),
+            ("total", self.total),
+        ]
+        .iter()
+        .filter_map(|(name, timeout)| timeout.map(|t| format!("{name} {} ms", t.as_millis())))
+        .collect();
+        write!(f, "{}", timeouts.join(", "))
+    }
+}
+
+/// A timeout of zero milliseconds disables the timeout
+fn millis(ms: u64) -> Option<Duration> {
+    if ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OptionalConfig {
+        OptionalConfig {
+            connect_timeout: Some(1000),
+            timeout: Some(0),
+            timeout_severity: Some(Severity::Warn),
+            ..OptionalConfig::default()
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        let timeouts = Timeouts::new(&OptionalConfig::default(), None);
+        assert_eq!(Timeouts::default(), timeouts);
+    }
+
+    #[test]
+    fn global() {
+        let timeouts = Timeouts::new(&config(), None);
+        assert_eq!(
+            Timeouts {
+                connect: Some(Duration::from_secs(1)),
+                read: Some(Duration::from_secs(30)),
+                total: None,
+                severity: Severity::Warn,
+            },
+            timeouts
+        );
+        assert_eq!("connect 1000 ms, read 30000 ms", timeouts.to_string());
+    }
+
+    #[test]
+    fn host_override() {
+        let host = HostConfig {
+            host: "*.slow.com".to_string(),
+            read_timeout: Some(90_000),
+            timeout: Some(120_000),
+            ..HostConfig::default()
+        };
+        let timeouts = Timeouts::new(&config(), Some(&host));
+        assert_eq!(
+            Timeouts {
+                connect: Some(Duration::from_secs(1)),
+                read: Some(Duration::from_secs(90)),
+                total: Some(Duration::from_secs(120)),
+                severity: Severity::Warn,
+            },
+            timeouts
+        );
+    }
+}
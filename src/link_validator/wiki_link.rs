@@ -0,0 +1,248 @@
+use crate::link_extractors::slug::{SlugStyle, Slugger};
+use crate::Config;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use walkdir::WalkDir;
+
+/// How the file a wiki link like `[[Page]]` points to is found
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WikiLinkResolution {
+    /// The only file whose path ends with the target, like Obsidian does
+    Shortest,
+    /// The target is a path relative to the root of the vault
+    Root,
+    /// The target is a path relative to the linking file
+    Relative,
+}
+
+impl FromStr for WikiLinkResolution {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<WikiLinkResolution, ()> {
+        match s {
+            "shortest" => Ok(WikiLinkResolution::Shortest),
+            "root" => Ok(WikiLinkResolution::Root),
+            "relative" => Ok(WikiLinkResolution::Relative),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Files of the vault which wiki links are resolved against.
+///
+/// The vault is the root dir if set or the checked directory otherwise.
+/// Hidden files and directories like `.git` or `.obsidian` are not part of it.
+pub struct WikiIndex {
+    resolution: WikiLinkResolution,
+    root: PathBuf,
+    /// Paths relative to the root with `/` as separator
+    files: Vec<String>,
+}
+
+impl WikiIndex {
+    #[must_use]
+    pub fn new(config: &Config, resolution: WikiLinkResolution) -> WikiIndex {
+        let root = match &config.optional.root_dir {
+            Some(root_dir) => root_dir.clone(),
+            None => config.directory.clone(),
+        };
+        let root = fs::canonicalize(&root).unwrap_or(root);
+        let mut files: Vec<String> = WalkDir::new(&root)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let relative = e.path().strip_prefix(&root).ok()?;
+                let segments: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect();
+                Some(segments.join("/"))
+            })
+            .collect();
+        files.sort();
+        WikiIndex {
+            resolution,
+            root,
+            files,
+        }
+    }
+
+    /// Resolve a wiki link target like `Page#Heading` to the path of the file.
+    /// The heading is turned into the fragment of its anchor.
+    pub fn resolve(
+        &self,
+        source: &str,
+        target: &str,
+        slug_style: SlugStyle,
+    ) -> Result<String, String> {
+        let (name, heading) = match target.split_once('#') {
+            Some((name, heading)) => (name.trim(), Some(heading)),
+            None => (target.trim(), None),
+        };
+        let file = if name.is_empty() {
+            fs::canonicalize(source).map_err(|e| e.to_string())?
+        } else {
+            self.find_file(source, name)?
+        };
+        let file = file.to_string_lossy();
+        // Nested headings `[[Page#Chapter#Section]]` link to the last one.
+        // Block references `[[Page#^block]]` have no anchor to check.
+        let heading = heading
+            .and_then(|heading| heading.rsplit('#').next())
+            .map(str::trim)
+            .filter(|heading| !heading.is_empty() && !heading.starts_with('^'));
+        match heading {
+            Some(heading) => Ok(format!("{file}#{}", Slugger::new(slug_style).slug(heading))),
+            None => Ok(file.to_string()),
+        }
+    }
+
+    fn find_file(&self, source: &str, name: &str) -> Result<PathBuf, String> {
+        let name = name.replace('\\', "/");
+        let candidates = file_names(name.trim_start_matches('/'));
+        let not_found = || format!("Wiki link target '{name}' not found.");
+        match self.resolution {
+            WikiLinkResolution::Root => find_in_dir(&self.root, &candidates).ok_or_else(not_found),
+            WikiLinkResolution::Relative => {
+                let source = fs::canonicalize(source).map_err(|e| e.to_string())?;
+                let dir = source.parent().unwrap_or_else(|| Path::new("/"));
+                find_in_dir(dir, &candidates).ok_or_else(not_found)
+            }
+            WikiLinkResolution::Shortest => {
+                let candidates: Vec<String> = candidates.iter().map(|c| c.to_lowercase()).collect();
+                let files = self.files.iter().map(|file| (file, file.to_lowercase()));
+                let (exact, partial): (Vec<_>, Vec<_>) = files
+                    .filter(|(_, file)| {
+                        candidates.iter().any(|candidate| {
+                            file == candidate || file.ends_with(&format!("/{candidate}"))
+                        })
+                    })
+                    .partition(|(_, file)| candidates.contains(file));
+                let matches = if exact.is_empty() { partial } else { exact };
+                match matches.as_slice() {
+                    [] => Err(not_found()),
+                    [(file, _)] => Ok(self.root.join(file)),
+                    _ => {
+                        let files: Vec<&str> =
+                            matches.iter().map(|(file, _)| file.as_str()).collect();
+                        Err(format!(
+                            "Wiki link target '{name}' is ambiguous. It matches {}.",
+                            files.join(", ")
+                        ))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Names of the files a wiki link target may refer to. Notes are markdown
+/// files without extension in the link. GitHub wikis replace the spaces of
+/// page names with hyphens.
+fn file_names(name: &str) -> Vec<String> {
+    let mut names = vec![name.to_string(), format!("{name}.md")];
+    if name.contains(' ') {
+        let hyphenated = name.replace(' ', "-");
+        names.push(format!("{hyphenated}.md"));
+        names.push(hyphenated);
+    }
+    names
+}
+
+fn find_in_dir(dir: &Path, candidates: &[String]) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .map(|candidate| dir.join(candidate))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OptionalConfig;
+    use ntest::test_case;
+
+    fn wiki_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches")
+            .join("wiki")
+    }
+
+    fn index(resolution: WikiLinkResolution) -> WikiIndex {
+        let config = Config {
+            directory: wiki_dir(),
+            optional: OptionalConfig::default(),
+        };
+        WikiIndex::new(&config, resolution)
+    }
+
+    fn resolve(
+        resolution: WikiLinkResolution,
+        source: &str,
+        target: &str,
+    ) -> Result<String, String> {
+        let source = wiki_dir().join(source);
+        let resolved =
+            index(resolution).resolve(source.to_str().unwrap(), target, SlugStyle::GitHub)?;
+        let root = fs::canonicalize(wiki_dir()).unwrap();
+        let relative = resolved.strip_prefix(root.to_str().unwrap()).unwrap();
+        Ok(relative.replace('\\', "/"))
+    }
+
+    #[test_case("Home", "/Home.md")]
+    #[test_case("home", "/Home.md")]
+    #[test_case("Getting Started", "/Getting-Started.md")]
+    #[test_case("notes/Ideas", "/notes/Ideas.md")]
+    #[test_case("archive/notes/Ideas", "/archive/notes/Ideas.md")]
+    #[test_case("diagram.png", "/assets/diagram.png")]
+    #[test_case("notes/Ideas.md#Second idea", "/notes/Ideas.md#second-idea")]
+    #[test_case("Home#Welcome#Details", "/Home.md#details")]
+    #[test_case("Home#^block-id", "/Home.md")]
+    fn shortest(target: &str, expected: &str) {
+        let resolved = resolve(WikiLinkResolution::Shortest, "Home.md", target);
+        assert_eq!(Ok(expected.to_string()), resolved);
+    }
+
+    #[test]
+    fn shortest_ambiguous() {
+        let resolved = resolve(WikiLinkResolution::Shortest, "Home.md", "Ideas");
+        let expected = "Wiki link target 'Ideas' is ambiguous. It matches archive/notes/Ideas.md, notes/Ideas.md.";
+        assert_eq!(Err(expected.to_string()), resolved);
+    }
+
+    #[test_case("shortest", "Missing")]
+    #[test_case("root", "Ideas")]
+    #[test_case("relative", "Home")]
+    fn not_found(resolution: &str, target: &str) {
+        let resolution = resolution.parse().unwrap();
+        let resolved = resolve(resolution, "notes/Ideas.md", target);
+        assert_eq!(
+            Err(format!("Wiki link target '{target}' not found.")),
+            resolved
+        );
+    }
+
+    #[test]
+    fn root() {
+        let resolved = resolve(WikiLinkResolution::Root, "notes/Ideas.md", "/notes/Ideas");
+        assert_eq!(Ok("/notes/Ideas.md".to_string()), resolved);
+    }
+
+    #[test]
+    fn relative() {
+        let resolved = resolve(WikiLinkResolution::Relative, "notes/Ideas.md", "../Home");
+        assert!(resolved.unwrap().ends_with("/notes/../Home.md"));
+    }
+
+    #[test]
+    fn same_file() {
+        let resolved = resolve(WikiLinkResolution::Shortest, "Home.md", "#Welcome");
+        assert_eq!(Ok("/Home.md#welcome".to_string()), resolved);
+    }
+}
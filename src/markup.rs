@@ -1,4 +1,6 @@
+use crate::link_extractors::source_code_link_extractor::CommentSyntax;
 use serde::Deserialize;
+use std::path::Path;
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -11,6 +13,14 @@ pub struct MarkupFile {
 pub enum MarkupType {
     Markdown,
     Html,
+    RestructuredText,
+    AsciiDoc,
+    Notebook,
+    Mdx,
+    RustDoc,
+    Latex,
+    StructuredData,
+    SourceCode,
 }
 
 impl FromStr for MarkupType {
@@ -20,12 +30,41 @@ impl FromStr for MarkupType {
         match s {
             "md" => Ok(MarkupType::Markdown),
             "html" => Ok(MarkupType::Html),
+            "rst" => Ok(MarkupType::RestructuredText),
+            "adoc" => Ok(MarkupType::AsciiDoc),
+            "ipynb" => Ok(MarkupType::Notebook),
+            "mdx" => Ok(MarkupType::Mdx),
+            "rs" => Ok(MarkupType::RustDoc),
+            "tex" => Ok(MarkupType::Latex),
+            "data" => Ok(MarkupType::StructuredData),
+            "code" => Ok(MarkupType::SourceCode),
             _ => Err(()),
         }
     }
 }
 
 impl MarkupType {
+    /// Markup type matching the file extension of the given path
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<MarkupType> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        [
+            MarkupType::Markdown,
+            MarkupType::Html,
+            MarkupType::RestructuredText,
+            MarkupType::AsciiDoc,
+            MarkupType::Notebook,
+            MarkupType::Mdx,
+            MarkupType::RustDoc,
+            MarkupType::Latex,
+            MarkupType::StructuredData,
+            MarkupType::SourceCode,
+        ]
+        .iter()
+        .copied()
+        .find(|t| t.file_extensions().contains(&ext))
+    }
+
     #[must_use]
     pub fn file_extensions(&self) -> Vec<String> {
         match self {
@@ -42,6 +81,19 @@ impl MarkupType {
                 "rmd".to_string(),
             ],
             MarkupType::Html => vec!["htm".to_string(), "html".to_string(), "xhtml".to_string()],
+            MarkupType::RestructuredText => vec!["rst".to_string(), "rest".to_string()],
+            MarkupType::AsciiDoc => vec!["adoc".to_string(), "asciidoc".to_string()],
+            MarkupType::Notebook => vec!["ipynb".to_string()],
+            MarkupType::Mdx => vec!["mdx".to_string()],
+            MarkupType::RustDoc => vec!["rs".to_string()],
+            MarkupType::Latex => vec!["tex".to_string()],
+            MarkupType::StructuredData => vec![
+                "yaml".to_string(),
+                "yml".to_string(),
+                "json".to_string(),
+                "toml".to_string(),
+            ],
+            MarkupType::SourceCode => CommentSyntax::file_extensions(),
         }
     }
 }
@@ -0,0 +1,234 @@
+use crate::link_validator::host::HostConfig;
+use crate::OptionalConfig;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+use tokio::time::{sleep_until, Duration, Instant};
+
+/// Default number of link checks which run at the same time
+pub const DEFAULT_MAX_CONCURRENCY: usize = 20;
+
+struct HostState {
+    /// Requests which may run at the same time. `None` if unlimited.
+    permits: Option<Arc<Semaphore>>,
+    throttle: Duration,
+    /// Earliest time the next request may start
+    next_request: Option<Instant>,
+}
+
+/// Decides when a link check may start.
+///
+/// At most `max-concurrency` checks run at the same time. Web requests are
+/// additionally limited per host by `max-concurrency-per-host` and spaced by
+/// the `throttle` delay. Both can be overridden in `[[hosts]]` blocks.
+pub struct Scheduler {
+    max_concurrency: usize,
+    permits: Semaphore,
+    max_concurrency_per_host: Option<usize>,
+    throttle: Duration,
+    hosts: Vec<HostConfig>,
+    host_states: Mutex<HashMap<String, HostState>>,
+}
+
+/// Permission to run a link check. The slots are released when it is dropped.
+pub struct Permit<'a> {
+    _global: SemaphorePermit<'a>,
+    _host: Option<OwnedSemaphorePermit>,
+}
+
+impl Scheduler {
+    #[must_use]
+    pub fn new(config: &OptionalConfig) -> Self {
+        let max_concurrency = config
+            .max_concurrency
+            .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+            .max(1);
+        Scheduler {
+            max_concurrency,
+            permits: Semaphore::new(max_concurrency),
+            max_concurrency_per_host: config.max_concurrency_per_host,
+            throttle: Duration::from_millis(config.throttle.unwrap_or_default().into()),
+            hosts: config.hosts.clone().unwrap_or_default(),
+            host_states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of link checks which may run at the same time
+    #[must_use]
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    fn host_state(&self, host: &str) -> HostState {
+        let host_config = HostConfig::find(&self.hosts, host);
+        let max_concurrency = host_config
+            .and_then(|c| c.max_concurrency)
+            .or(self.max_concurrency_per_host);
+        let throttle = host_config
+            .and_then(|c| c.throttle)
+            .map_or(self.throttle, |ms| Duration::from_millis(ms.into()));
+        HostState {
+            permits: max_concurrency.map(|max| Arc::new(Semaphore::new(max.max(1)))),
+            throttle,
+            next_request: None,
+        }
+    }
+
+    /// Wait until a check may start. Checks of web links pass the host of
+    /// the URL to apply the limits of the host. The throttle slot is reserved
+    /// after both permits are taken, so that requests which waited for a
+    /// permit still start with the delay in between.
+    pub async fn acquire(&self, host: Option<&str>) -> Permit<'_> {
+        let mut host_permit = None;
+        if let Some(host) = host {
+            let permits = self
+                .host_states
+                .lock()
+                .await
+                .entry(host.to_string())
+                .or_insert_with(|| self.host_state(host))
+                .permits
+                .clone();
+            if let Some(permits) = permits {
+                host_permit = Some(
+                    permits
+                        .acquire_owned()
+                        .await
+                        .expect("Bug! host semaphore closed"),
+                );
+            }
+        }
+        let global_permit = self.permits.acquire().await.expect("Bug! semaphore closed");
+
+        if let Some(host) = host {
+            let wait_until = {
+                let mut host_states = self.host_states.lock().await;
+                let state = host_states.get_mut(host).expect("Bug! host state missing");
+                if state.throttle.is_zero() {
+                    None
+                } else {
+                    let now = Instant::now();
+                    let start = state.next_request.map_or(now, |next| next.max(now));
+                    state.next_request = Some(start + state.throttle);
+                    Some(start)
+                }
+            };
+            if let Some(deadline) = wait_until {
+                sleep_until(deadline).await;
+            }
+        }
+        Permit {
+            _global: global_permit,
+            _host: host_permit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::timeout;
+
+    const BLOCKED: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn max_concurrency() {
+        let scheduler = Scheduler::new(&OptionalConfig {
+            max_concurrency: Some(1),
+            ..OptionalConfig::default()
+        });
+        let permit = scheduler.acquire(None).await;
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("a.com")))
+            .await
+            .is_err());
+        drop(permit);
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("a.com")))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn max_concurrency_per_host() {
+        let scheduler = Scheduler::new(&OptionalConfig {
+            max_concurrency_per_host: Some(2),
+            hosts: Some(vec![HostConfig {
+                host: "*.internal".to_string(),
+                max_concurrency: Some(1),
+                ..HostConfig::default()
+            }]),
+            ..OptionalConfig::default()
+        });
+        let _internal = scheduler.acquire(Some("docs.internal")).await;
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("docs.internal")))
+            .await
+            .is_err());
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("wiki.internal")))
+            .await
+            .is_ok());
+
+        let _first = scheduler.acquire(Some("cdn.com")).await;
+        let _second = scheduler.acquire(Some("cdn.com")).await;
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("cdn.com")))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn throttle() {
+        let scheduler = Scheduler::new(&OptionalConfig {
+            throttle: Some(1000),
+            hosts: Some(vec![HostConfig {
+                host: "fast.com".to_string(),
+                throttle: Some(0),
+                ..HostConfig::default()
+            }]),
+            ..OptionalConfig::default()
+        });
+        drop(scheduler.acquire(Some("slow.com")).await);
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("slow.com")))
+            .await
+            .is_err());
+        drop(scheduler.acquire(Some("fast.com")).await);
+        assert!(timeout(BLOCKED, scheduler.acquire(Some("fast.com")))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn throttle_after_max_concurrency() {
+        let throttle = Duration::from_millis(200);
+        let scheduler = Arc::new(Scheduler::new(&OptionalConfig {
+            max_concurrency: Some(1),
+            hosts: Some(vec![HostConfig {
+                host: "slow.com".to_string(),
+                throttle: Some(200),
+                ..HostConfig::default()
+            }]),
+            ..OptionalConfig::default()
+        }));
+        let busy = scheduler.acquire(Some("busy.com")).await;
+        let checks: Vec<_> = (0..2)
+            .map(|_| {
+                let scheduler = Arc::clone(&scheduler);
+                tokio::spawn(async move {
+                    drop(scheduler.acquire(Some("slow.com")).await);
+                    Instant::now()
+                })
+            })
+            .collect();
+        // Wait longer than the throttle while all permits are taken
+        tokio::time::sleep(throttle * 2).await;
+        drop(busy);
+
+        let mut starts = vec![];
+        for check in checks {
+            starts.push(check.await.unwrap());
+        }
+        starts.sort();
+        assert!(
+            starts[1] - starts[0] >= throttle - Duration::from_millis(10),
+            "{:?}",
+            starts[1] - starts[0]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 mod helper;
 
 use helper::benches_dir;
+use mlc::link_validator::wiki_link::WikiLinkResolution;
 use mlc::markup::MarkupType;
 use mlc::Config;
 use mlc::OptionalConfig;
@@ -30,6 +31,27 @@ async fn end_to_end() {
             csv_file: None,
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
     if let Err(e) = mlc::run(&config).await {
@@ -58,6 +80,27 @@ async fn end_to_end_different_root() {
             csv_file: Some(csv_output.clone()),
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
     if let Err(e) = mlc::run(&config).await {
@@ -67,7 +110,7 @@ async fn end_to_end_different_root() {
         let content = fs::read_to_string(csv_output).unwrap();
         let lines: Vec<&str> = content.lines().collect();
         assert_eq!(lines.len(), 1);
-        assert_eq!(lines[0], "source,line,column,target,severity");
+        assert_eq!(lines[0], "source,line,column,target,severity,status");
     }
 }
 
@@ -91,18 +134,39 @@ async fn end_to_end_write_csv_file() {
             csv_file: Some(csv_output.clone()),
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
     if (mlc::run(&config).await).is_err() {
         let content = fs::read_to_string(csv_output).unwrap();
         let lines: Vec<&str> = content.lines().collect();
         assert_eq!(lines.len(), 4);
-        assert_eq!(lines[0], "source,line,column,target,severity");
+        assert_eq!(lines[0], "source,line,column,target,severity,status");
         for (i, line) in lines.iter().enumerate().skip(1) {
             assert_eq!(
                 line,
                 &format!(
-                    "benches{MAIN_SEPARATOR}benchmark/markdown/ignore_me.md,{i},1,broken_Link,ERR",
+                    "benches{MAIN_SEPARATOR}benchmark/markdown/ignore_me.md,{i},1,broken_Link,ERR,",
                 )
             );
         }
@@ -131,6 +195,27 @@ async fn end_to_end_csv_include_warnings() {
             csv_file: Some(csv_output.clone()),
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
     // Run the check - should succeed because we're offline
@@ -147,7 +232,7 @@ async fn end_to_end_csv_include_warnings() {
         lines.len() > 1,
         "CSV should have header and warning entries"
     );
-    assert_eq!(lines[0], "source,line,column,target,severity");
+    assert_eq!(lines[0], "source,line,column,target,severity,status");
 
     // Verify that warning entries are present - the ref_links.md file has several broken markdown references
     // Check that all lines after header have the expected CSV format with severity column
@@ -155,14 +240,18 @@ async fn end_to_end_csv_include_warnings() {
         let parts: Vec<&str> = line.split(',').collect();
         assert_eq!(
             parts.len(),
-            5,
-            "Each CSV line should have 5 columns including severity"
+            6,
+            "Each CSV line should have 6 columns including severity and status"
         );
         assert!(
             parts[0].contains("ref_links.md"),
             "Source should be ref_links.md"
         );
         assert_eq!(parts[4], "WARN", "Severity should be WARN for warnings");
+        assert_eq!(
+            parts[5], "",
+            "Status should be empty for links without response"
+        );
     }
 
     // Verify specific warnings are captured (broken markdown references)
@@ -177,3 +266,197 @@ async fn end_to_end_csv_include_warnings() {
     // Also verify the test would pass
     assert!(result.is_ok(), "Should succeed with warnings only");
 }
+
+#[tokio::test]
+async fn end_to_end_wiki_links() {
+    let config = Config {
+        directory: benches_dir().join("wiki"),
+        optional: OptionalConfig {
+            debug: None,
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true), // Use offline mode to avoid checking external URLs
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: None,
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: Some(WikiLinkResolution::Shortest),
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_latex() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("latex"),
+        optional: OptionalConfig {
+            debug: None,
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::Latex]),
+            offline: Some(true), // Use offline mode to avoid checking external URLs
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: None,
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_structured_data() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("data"),
+        optional: OptionalConfig {
+            debug: None,
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::StructuredData]),
+            offline: Some(true), // Use offline mode to avoid checking external URLs
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: None,
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: Some(vec![
+                "project.toml=package.homepage,package.readme"
+                    .parse()
+                    .unwrap(),
+                "*.json=bugs.url,files.*".parse().unwrap(),
+                "*ISSUE_TEMPLATE*=contact_links.*.url".parse().unwrap(),
+            ]),
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_source_code() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("code"),
+        optional: OptionalConfig {
+            debug: None,
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::SourceCode]),
+            offline: Some(true), // Use offline mode to avoid checking external URLs
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: None,
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test failed. {:?}", e);
+    }
+}
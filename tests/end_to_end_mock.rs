@@ -2,6 +2,7 @@
 mod helper;
 
 use helper::benches_dir;
+use mlc::link_validator::Severity;
 use mlc::markup::MarkupType;
 use mlc::Config;
 use mlc::OptionalConfig;
@@ -105,6 +106,27 @@ async fn end_to_end_with_mock_servers() {
             csv_file: None,
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
 
@@ -160,6 +182,27 @@ async fn end_to_end_with_mock_server_failure() {
             csv_file: None,
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
 
@@ -172,6 +215,89 @@ async fn end_to_end_with_mock_server_failure() {
     fs::remove_dir_all(&temp_dir).unwrap();
 }
 
+#[tokio::test]
+async fn end_to_end_with_mock_server_failure_in_code_block() {
+    // Set up a mock server that returns 404
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("HEAD", "/")
+        .with_status(404)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    // Create temporary directory for test files
+    let temp_dir = std::env::temp_dir().join("mlc_test_mock_code_block");
+    if temp_dir.exists() {
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+    fs::create_dir_all(&temp_dir).unwrap();
+
+    // Create a test file with a broken link in a shell code block
+    let content = format!("```sh\ncurl -L \"{}\" | sh\n```", server.url());
+    fs::write(temp_dir.join("install.md"), content).unwrap();
+
+    let config = |severity: Option<Severity>| Config {
+        directory: temp_dir.clone(),
+        optional: OptionalConfig {
+            debug: Some(true),
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: None,
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: None,
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: Some(vec!["sh".to_string()]),
+            code_block_severity: severity,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
+        },
+    };
+
+    // Broken links of code blocks are errors by default
+    if mlc::run(&config(None)).await.is_ok() {
+        panic!("Test should have failed due to 404 response from mock server");
+    }
+    // The severity of broken links of code blocks can be lowered
+    if let Err(e) = mlc::run(&config(Some(Severity::Warn))).await {
+        panic!(
+            "Test should succeed with code block severity 'warn'. {:?}",
+            e
+        );
+    }
+
+    // Clean up
+    fs::remove_dir_all(&temp_dir).unwrap();
+}
+
 #[tokio::test]
 async fn end_to_end_with_mock_server_redirect() {
     // Set up redirect and target mock servers
@@ -229,6 +355,27 @@ async fn end_to_end_with_mock_server_redirect() {
             csv_file: None,
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
 
@@ -243,3 +390,97 @@ async fn end_to_end_with_mock_server_redirect() {
     // Clean up
     fs::remove_dir_all(&temp_dir).unwrap();
 }
+
+#[tokio::test]
+async fn end_to_end_with_mock_server_csv_status() {
+    let mut server = mockito::Server::new_async().await;
+    for (method, path, status) in [
+        ("HEAD", "/missing", 404),
+        ("GET", "/missing", 404),
+        ("HEAD", "/bot", 403),
+        ("GET", "/bot", 403),
+    ] {
+        server
+            .mock(method, path)
+            .with_status(status)
+            .create_async()
+            .await;
+    }
+
+    let temp_dir = std::env::temp_dir().join("mlc_test_mock_csv_status");
+    if temp_dir.exists() {
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+    fs::create_dir_all(&temp_dir).unwrap();
+    let content = format!(
+        "[Missing]({url}/missing)\n[Bot]({url}/bot)\n[Local](./missing.md)\n",
+        url = server.url()
+    );
+    fs::write(temp_dir.join("status.md"), content).unwrap();
+    let csv_output = temp_dir.join("status.csv");
+
+    let config = Config {
+        directory: temp_dir.join("status.md"),
+        optional: OptionalConfig {
+            debug: None,
+            do_not_warn_for_redirect_to: None,
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: None,
+            match_file_extension: None,
+            throttle: None,
+            ignore_links: None,
+            ignore_path: None,
+            root_dir: None,
+            gitignore: None,
+            gituntracked: None,
+            csv_file: Some(csv_output.clone()),
+            files: None,
+            http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: Some(vec!["403=warn".parse().unwrap()]),
+            hosts: None,
+        },
+    };
+    if mlc::run(&config).await.is_ok() {
+        panic!("Test should have failed due to 404 response from mock server");
+    }
+
+    let content = fs::read_to_string(&csv_output).unwrap();
+    let mut lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.remove(0), "source,line,column,target,severity,status");
+    let mut rows: Vec<Vec<&str>> = lines
+        .iter()
+        .map(|line| line.split(',').skip(1).collect())
+        .collect();
+    rows.sort();
+    let missing = format!("{}/missing", server.url());
+    let bot = format!("{}/bot", server.url());
+    assert_eq!(
+        vec![
+            vec!["1", "1", missing.as_str(), "ERR", "404"],
+            vec!["2", "1", bot.as_str(), "WARN", "403"],
+            vec!["3", "1", "./missing.md", "ERR", ""],
+        ],
+        rows
+    );
+
+    fs::remove_dir_all(&temp_dir).unwrap();
+}
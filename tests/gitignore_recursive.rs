@@ -106,6 +106,27 @@ async fn gitignore_is_recursive_nested_gitignore_is_respected() {
             throttle: None,
             files: None,
             http_headers: None,
+            check_web_anchors: None,
+            ignore_anchors: None,
+            slug_style: None,
+            autolink_literals: None,
+            code_block_languages: None,
+            code_block_severity: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            structured_data: None,
+            retries: None,
+            retry_delay: None,
+            retry_max_wait: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            timeout_severity: None,
+            max_concurrency: None,
+            max_concurrency_per_host: None,
+            accept_status: None,
+            status_map: None,
+            hosts: None,
         },
     };
 
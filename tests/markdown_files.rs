@@ -1,6 +1,7 @@
 #[cfg(test)]
 use mlc::link_extractors::link_extractor::find_links;
 use mlc::markup::{MarkupFile, MarkupType};
+use mlc::Config;
 
 #[test]
 fn no_links() {
@@ -9,7 +10,7 @@ fn no_links() {
         path,
         markup_type: MarkupType::Markdown,
     };
-    let result = find_links(&file);
+    let result = find_links(&file, &Config::default());
     assert!(result.is_empty());
 }
 
@@ -20,6 +21,6 @@ fn some_links() {
         path,
         markup_type: MarkupType::Markdown,
     };
-    let result = find_links(&file);
-    assert_eq!(result.len(), 12);
+    let result = find_links(&file, &Config::default());
+    assert_eq!(result.len(), 13);
 }